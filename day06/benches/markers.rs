@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use day06::{check_for_length, scan};
+
+// Letters from an alphabet too small to contain a marker of `length`, then a
+// run of `a` and one marker starting with `a` at the very end, so the whole
+// stream has to be scanned.
+fn generate(size: usize, length: usize, seed: u64) -> String {
+    let alphabet = (length - 1) as u64;
+    let mut state = seed;
+    let mut stream: String = (0..size - 2 * length + 1)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (b'a' + ((state >> 33) % alphabet) as u8) as char
+        })
+        .collect();
+    stream.extend((1..length).map(|_| 'a'));
+    stream.push('a');
+    stream.extend((1..length).map(|offset| (b'z' + 1 - offset as u8) as char));
+    stream
+}
+
+// The original detector: a fresh map per window and `chars().nth` per letter.
+fn hash_map_check(transmission: &str, length: usize) -> usize {
+    let index_ref = length - 1;
+    'outer: for index in index_ref..transmission.len() {
+        let mut checker: HashMap<String, bool> = HashMap::new();
+        for check in index - index_ref..=index {
+            let current = transmission.chars().nth(check).unwrap();
+            if checker.contains_key(&current.to_string()) {
+                continue 'outer;
+            }
+            checker.insert(current.to_string(), true);
+        }
+        return index + 1;
+    }
+    0
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>6} {:>14} {:>12} {:>14} {:>14}",
+        "bytes", "length", "sliding", "MB/s", "stream", "hash map"
+    );
+    for size in [10_000, 1 << 20, 8 << 20, 32 << 20] {
+        for length in [4, 14] {
+            let stream = generate(size, length, size as u64);
+            let (position, elapsed) = time(|| check_for_length(&stream, length));
+            assert_eq!(position, Some(size));
+            let (streamed, stream_time) = time(|| scan(stream.as_bytes(), &[length], |_, _| ()).unwrap());
+            assert_eq!(streamed, vec![Some(size)]);
+            // The original is quadratic, so it only runs on the smallest stream.
+            let reference = if size <= 10_000 {
+                let (reference, elapsed) = time(|| hash_map_check(&stream, length));
+                assert_eq!(Some(reference), position);
+                format!("{:?}", elapsed)
+            } else {
+                "-".to_string()
+            };
+            let throughput = size as f64 / elapsed.as_secs_f64() / (1 << 20) as f64;
+            println!(
+                "{:>10} {:>6} {:>14?} {:>12.1} {:>14?} {:>14}",
+                size, length, elapsed, throughput, stream_time, reference
+            );
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+// How many symbols the longest all-distinct window ending at every position has.
+// `remember(symbol, index)` records where a symbol was seen and returns where it was seen before.
+fn run_lengths<S>(symbols: impl Iterator<Item = S>, mut remember: impl FnMut(S, usize) -> Option<usize>) -> Vec<usize> {
+    let mut start = 0;
+    symbols
+        .enumerate()
+        .map(|(index, symbol)| {
+            if let Some(previous) = remember(symbol, index) {
+                start = start.max(previous + 1);
+            }
+            index + 1 - start
+        })
+        .collect()
+}
+
+// Per character, with a byte table for ASCII and a hash map otherwise.
+fn distinct_run_lengths(transmission: &str) -> Vec<usize> {
+    if transmission.is_ascii() {
+        let mut last_seen = [None; 256];
+        run_lengths(transmission.bytes(), |byte, index| last_seen[byte as usize].replace(index))
+    } else {
+        let mut last_seen = HashMap::new();
+        run_lengths(transmission.chars(), |symbol, index| last_seen.insert(symbol, index))
+    }
+}
+
+/// The longest stretch of distinct characters as a byte range of `transmission`,
+/// the first one on ties; empty for an empty transmission.
+pub fn longest_distinct_window(transmission: &str) -> Range<usize> {
+    let (mut end, mut longest) = (0, 0);
+    for (index, length) in distinct_run_lengths(transmission).into_iter().enumerate() {
+        if length > longest {
+            (end, longest) = (index + 1, length);
+        }
+    }
+    // Character positions to byte offsets.
+    let offset = |position: usize| transmission.char_indices().nth(position).map_or(transmission.len(), |(byte, _)| byte);
+    offset(end - longest)..offset(end)
+}
+
+/// `counts[length]` is the number of windows of `length` distinct characters; the
+/// last entry is for the longest such window and `counts[0]` is always 0.
+pub fn window_counts(transmission: &str) -> Vec<usize> {
+    // A window of distinct letters ending somewhere contains shorter ones ending
+    // there too, so count the longest per end and sum them up from the top.
+    let mut counts = Vec::new();
+    for length in distinct_run_lengths(transmission) {
+        if counts.len() <= length {
+            counts.resize(length + 1, 0);
+        }
+        counts[length] += 1;
+    }
+    for length in (1..counts.len().saturating_sub(1)).rev() {
+        counts[length] += counts[length + 1];
+    }
+    counts
+}
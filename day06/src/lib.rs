@@ -0,0 +1,13 @@
+mod analysis;
+mod marker;
+mod solution;
+mod stream;
+mod transmission;
+
+pub use analysis::{longest_distinct_window, window_counts};
+pub use marker::{
+    check_for_length, markers, Alphabet, ByteCounts, Counts, Detector, LowercaseCounts, Markers, UnicodeCounts, Window,
+};
+pub use solution::{solve_part1, solve_part2};
+pub use stream::scan;
+pub use transmission::Transmission;
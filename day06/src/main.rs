@@ -1,38 +1,135 @@
-use std::fs;
-use std::collections::HashMap;
-
-fn check_for_length(transmission: &String, length: usize) -> usize {
-    let index_ref = length - 1;
-    'outer: for index in index_ref..transmission.len() {
-        let mut checker: HashMap<String, bool> = HashMap::new();
-        for check in index-index_ref..=index {
-            let current = transmission.chars().nth(check).unwrap();
-            //println!("{} {}", check, current);
-            if checker.contains_key(&current.to_string()) {
-                continue 'outer;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::str::FromStr;
+
+use day06::{check_for_length, longest_distinct_window, markers, scan, window_counts, Transmission};
+
+const PARTS: [(&str, usize); 2] = [("Part 1", 4), ("Part 2", 14)];
+
+fn stream(args: &[String]) {
+    let usage = || -> ! {
+        eprintln!("Usage: day06 stream [--length N]... [file|-]");
+        eprintln!("  Reads stdin if no file is given, looks for markers of length 4 and 14 by default");
+        std::process::exit(2);
+    };
+    let mut lengths = Vec::new();
+    let mut path = None;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--length" => {
+                let length = rest.next().and_then(|value| usize::from_str(value).ok());
+                lengths.push(length.filter(|length| *length > 0).unwrap_or_else(|| usage()));
             }
-            checker.insert(current.to_string(), true);
+            _ if path.is_none() => path = Some(arg.as_str()),
+            _ => usage(),
+        }
+    }
+    if lengths.is_empty() {
+        lengths = PARTS.iter().map(|(_, length)| *length).collect();
+    }
+
+    let report = |length, position| println!("Marker of length {} after {} characters", length, position);
+    let positions = match path {
+        None | Some("-") => scan(io::stdin().lock(), &lengths, report),
+        Some(path) => scan(File::open(path).expect("Cannot read file"), &lengths, report),
+    }
+    .expect("Cannot read transmission");
+    for (length, position) in lengths.iter().zip(positions) {
+        if position.is_none() {
+            println!("No marker of length {}", length);
         }
-        return index + 1;
     }
-    return 0;
 }
 
-fn part1(transmission: &String) {
-    println!("Part 1: {}", check_for_length(transmission, 4));
+fn analyze(args: &[String]) {
+    if args.len() != 1 {
+        eprintln!("Usage: day06 analyze <file>");
+        std::process::exit(2);
+    }
+    let content = fs::read_to_string(&args[0]).expect("Cannot read file");
+    let transmission = content.trim_end();
+
+    for (_, length) in PARTS {
+        match check_for_length(transmission, length) {
+            Some(first) => println!(
+                "Length {}: first marker after {} characters, {} markers in total",
+                length,
+                first,
+                markers(transmission, length).count()
+            ),
+            None => println!("Length {}: no marker", length),
+        }
+    }
+    let longest = &transmission[longest_distinct_window(transmission)];
+    println!("Longest distinct window: {} characters ({})", longest.chars().count(), longest);
+    println!("Distinct windows per length:");
+    for (length, count) in window_counts(transmission).iter().enumerate().skip(1) {
+        println!("  {:>2} {}", length, count);
+    }
 }
 
-fn part2(transmission: &String) {
-    println!("Part 2: {}", check_for_length(transmission, 14));
+// Runs every line of `file` as its own transmission and checks the expected
+// positions where a line has them. Returns false if any of them is wrong.
+fn run_file(file: &str) -> bool {
+    let reader = BufReader::new(File::open(file).expect("Cannot read file"));
+    let mut correct = true;
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.expect("Cannot read file");
+        if line.trim().is_empty() {
+            continue;
+        }
+        let transmission = Transmission::from_str(&line).unwrap_or_else(|error| {
+            eprintln!("{}:{}: {}", file, index + 1, error);
+            std::process::exit(1);
+        });
+
+        let mut results = Vec::new();
+        let mut line_correct = true;
+        for (part_index, (part, length)) in PARTS.iter().enumerate() {
+            let position = check_for_length(&transmission.stream, *length);
+            let mut result = match position {
+                Some(position) => format!("{}: {}", part, position),
+                None => format!("{}: no marker", part),
+            };
+            if let Some(expected) = transmission.expected {
+                if position != Some(expected[part_index]) {
+                    result.push_str(&format!(" (expected {})", expected[part_index]));
+                    line_correct = false;
+                }
+            }
+            results.push(result);
+        }
+        let check = match (transmission.expected, line_correct) {
+            (None, _) => "",
+            (Some(_), true) => " ok",
+            (Some(_), false) => " MISMATCH",
+        };
+        println!("Line {}: {}{}", index + 1, results.join(", "), check);
+        correct &= line_correct;
+    }
+    correct
 }
 
 fn main() {
-    let files = vec!["sample.txt", "input.txt"];
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("stream") => return stream(&args[1..]),
+        Some("analyze") => return analyze(&args[1..]),
+        _ => (),
+    }
+
+    let files = match args.first() {
+        Some(_) => args.iter().map(String::as_str).collect(),
+        None => vec!["sample.txt", "samples.txt", "input.txt"],
+    };
+    let mut correct = true;
     for file in files {
         println!("Reading {}", file);
-        let content = fs::read_to_string(file).expect("Cannot read file");
-        println!("Transmission: {}", content);
-        part1(&content);
-        part2(&content);
+        correct &= run_file(file);
+    }
+    if !correct {
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+/// The symbols a transmission is made of, from the fastest to count to the most general.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Alphabet {
+    /// `a` to `z`, counted in a 26-entry table.
+    Lowercase,
+    /// Any ASCII character, counted in a byte table.
+    Bytes,
+    /// Any Unicode scalar value, counted in a hash map.
+    Unicode,
+}
+
+impl Alphabet {
+    /// The fastest alphabet that covers every character of `transmission`.
+    pub fn detect(transmission: &str) -> Self {
+        if transmission.bytes().all(|byte| byte.is_ascii_lowercase()) {
+            Alphabet::Lowercase
+        } else if transmission.is_ascii() {
+            Alphabet::Bytes
+        } else {
+            Alphabet::Unicode
+        }
+    }
+}
+
+/// Occurrence counts of the symbols in a window.
+pub trait Counts: Default {
+    type Symbol: Copy + Default;
+
+    /// Whether `symbol` can be counted at all.
+    fn accepts(_symbol: Self::Symbol) -> bool {
+        true
+    }
+
+    /// Counts one more `symbol`, returning its new count.
+    fn add(&mut self, symbol: Self::Symbol) -> u32;
+
+    /// Counts one less `symbol`, returning its new count.
+    fn remove(&mut self, symbol: Self::Symbol) -> u32;
+}
+
+/// Counts of `a` to `z`, indexed by the low five bits which differ for every letter.
+#[derive(Default)]
+pub struct LowercaseCounts([u32; 32]);
+
+impl Counts for LowercaseCounts {
+    type Symbol = u8;
+
+    fn accepts(byte: u8) -> bool {
+        byte.is_ascii_lowercase()
+    }
+
+    fn add(&mut self, byte: u8) -> u32 {
+        let count = &mut self.0[(byte & 31) as usize];
+        *count += 1;
+        *count
+    }
+
+    fn remove(&mut self, byte: u8) -> u32 {
+        let count = &mut self.0[(byte & 31) as usize];
+        *count -= 1;
+        *count
+    }
+}
+
+pub struct ByteCounts([u32; 256]);
+
+impl Default for ByteCounts {
+    fn default() -> Self {
+        ByteCounts([0; 256])
+    }
+}
+
+impl Counts for ByteCounts {
+    type Symbol = u8;
+
+    fn accepts(byte: u8) -> bool {
+        byte.is_ascii()
+    }
+
+    fn add(&mut self, byte: u8) -> u32 {
+        let count = &mut self.0[byte as usize];
+        *count += 1;
+        *count
+    }
+
+    fn remove(&mut self, byte: u8) -> u32 {
+        let count = &mut self.0[byte as usize];
+        *count -= 1;
+        *count
+    }
+}
+
+#[derive(Default)]
+pub struct UnicodeCounts(HashMap<char, u32>);
+
+impl Counts for UnicodeCounts {
+    type Symbol = char;
+
+    fn add(&mut self, symbol: char) -> u32 {
+        let count = self.0.entry(symbol).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    fn remove(&mut self, symbol: char) -> u32 {
+        let count = self.0.get_mut(&symbol).expect("Removing a symbol that is not in the window");
+        *count -= 1;
+        *count
+    }
+}
+
+/// Sliding window over the last `length` symbols of a transmission, fed incrementally.
+///
+/// Keeps a count per symbol and the number of symbols that occur more than
+/// once in the window, so every step is O(1).
+pub struct Window<C: Counts> {
+    symbols: Vec<C::Symbol>,
+    counts: C,
+    repeated: usize,
+    position: usize,
+    // Where the next symbol goes in `symbols`, overwriting the oldest one.
+    slot: usize,
+}
+
+impl<C: Counts> Window<C> {
+    pub fn new(length: usize) -> Self {
+        assert!(length > 0, "Marker length must be positive");
+        Window {
+            symbols: vec![C::Symbol::default(); length],
+            counts: C::default(),
+            repeated: 0,
+            position: 0,
+            slot: 0,
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Number of symbols consumed so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Consumes `symbols` up to the end of the next marker or the first symbol
+    /// the counts do not accept. Returns how many symbols were consumed and
+    /// whether they end with a marker.
+    pub fn advance(&mut self, symbols: &[C::Symbol]) -> (usize, bool) {
+        // Work on locals so the loop keeps its state in registers.
+        let length = self.symbols.len();
+        let mut counts = std::mem::take(&mut self.counts);
+        let (mut repeated, mut position, mut slot) = (self.repeated, self.position, self.slot);
+        let mut consumed = symbols.len();
+        let mut found = false;
+        for (index, symbol) in symbols.iter().enumerate() {
+            if !C::accepts(*symbol) {
+                consumed = index;
+                break;
+            }
+            if position >= length && counts.remove(self.symbols[slot]) == 1 {
+                repeated -= 1;
+            }
+            if counts.add(*symbol) == 2 {
+                repeated += 1;
+            }
+            self.symbols[slot] = *symbol;
+            slot += 1;
+            if slot == length {
+                slot = 0;
+            }
+            position += 1;
+            if position >= length && repeated == 0 {
+                consumed = index + 1;
+                found = true;
+                break;
+            }
+        }
+        self.counts = counts;
+        (self.repeated, self.position, self.slot) = (repeated, position, slot);
+        (consumed, found)
+    }
+
+    /// The same window counted with a different table, each symbol mapped by `map`.
+    fn convert<D: Counts>(&self, map: impl Fn(C::Symbol) -> D::Symbol) -> Window<D> {
+        let length = self.symbols.len();
+        let filled = self.position.min(length);
+        // Once the window is full, the oldest symbol is the one about to be overwritten.
+        let oldest = if self.position >= length { self.slot } else { 0 };
+        let mut window = Window::<D>::new(length);
+        for offset in 0..filled {
+            let symbol = map(self.symbols[(oldest + offset) % length]);
+            if window.counts.add(symbol) == 2 {
+                window.repeated += 1;
+            }
+            window.symbols[offset] = symbol;
+        }
+        window.slot = filled % length;
+        window.position = self.position;
+        window
+    }
+}
+
+enum ByteWindow {
+    Lowercase(Window<LowercaseCounts>),
+    // Boxed, the byte table is much larger and only needed once a non-letter arrives.
+    Bytes(Box<Window<ByteCounts>>),
+    Unicode(Box<Window<UnicodeCounts>>),
+}
+
+/// Sliding window over a byte stream. Counts lowercase letters in a small
+/// table until the first other byte arrives, then switches to a table of all
+/// bytes, and to decoding UTF-8 and counting characters at the first non-ASCII one.
+pub struct Detector {
+    window: ByteWindow,
+    // The start of a UTF-8 sequence split across slices.
+    partial: Vec<u8>,
+}
+
+impl Detector {
+    pub fn new(length: usize) -> Self {
+        Detector { window: ByteWindow::Lowercase(Window::new(length)), partial: Vec::new() }
+    }
+
+    pub fn length(&self) -> usize {
+        match &self.window {
+            ByteWindow::Lowercase(window) => window.length(),
+            ByteWindow::Bytes(window) => window.length(),
+            ByteWindow::Unicode(window) => window.length(),
+        }
+    }
+
+    /// Number of characters consumed so far.
+    pub fn position(&self) -> usize {
+        match &self.window {
+            ByteWindow::Lowercase(window) => window.position(),
+            ByteWindow::Bytes(window) => window.position(),
+            ByteWindow::Unicode(window) => window.position(),
+        }
+    }
+
+    /// The table the characters are counted in so far.
+    pub fn alphabet(&self) -> Alphabet {
+        match &self.window {
+            ByteWindow::Lowercase(_) => Alphabet::Lowercase,
+            ByteWindow::Bytes(_) => Alphabet::Bytes,
+            ByteWindow::Unicode(_) => Alphabet::Unicode,
+        }
+    }
+
+    /// Consumes one byte; true if the last `length` characters are all different.
+    pub fn push(&mut self, byte: u8) -> bool {
+        self.push_slice(&[byte]).is_some()
+    }
+
+    /// Consumes `bytes` up to the end of the first marker in them. Returns how
+    /// many bytes that took, or `None` if all of them were consumed without one.
+    pub fn push_slice(&mut self, bytes: &[u8]) -> Option<usize> {
+        let mut consumed = 0;
+        loop {
+            let (taken, found) = match &mut self.window {
+                ByteWindow::Lowercase(window) => window.advance(&bytes[consumed..]),
+                ByteWindow::Bytes(window) => window.advance(&bytes[consumed..]),
+                ByteWindow::Unicode(window) => {
+                    let taken = push_utf8(window, &mut self.partial, &bytes[consumed..]);
+                    return taken.map(|taken| consumed + taken);
+                }
+            };
+            consumed += taken;
+            if found {
+                return Some(consumed);
+            }
+            if consumed == bytes.len() {
+                return None;
+            }
+            // The byte tables stop early at a byte they cannot count.
+            self.window = match &self.window {
+                ByteWindow::Lowercase(window) if bytes[consumed].is_ascii() => {
+                    ByteWindow::Bytes(Box::new(window.convert(|byte| byte)))
+                }
+                ByteWindow::Lowercase(window) => ByteWindow::Unicode(Box::new(window.convert(char::from))),
+                ByteWindow::Bytes(window) => ByteWindow::Unicode(Box::new(window.convert(char::from))),
+                ByteWindow::Unicode(_) => unreachable!("The Unicode table takes every byte"),
+            };
+        }
+    }
+}
+
+// Decodes `bytes` after any sequence left over from the previous slice and
+// counts the characters up to the end of the first marker. Invalid sequences
+// count as U+FFFD, an incomplete one at the end waits for the next slice.
+fn push_utf8(window: &mut Window<UnicodeCounts>, partial: &mut Vec<u8>, bytes: &[u8]) -> Option<usize> {
+    let carried = partial.len();
+    let mut input = std::mem::take(partial);
+    input.extend_from_slice(bytes);
+    // Every character and the offset in `input` right after it.
+    let (mut chars, mut ends) = (Vec::new(), Vec::new());
+    let mut start = 0;
+    while start < input.len() {
+        let (valid, invalid) = match std::str::from_utf8(&input[start..]) {
+            Ok(text) => (text, None),
+            Err(error) => {
+                let valid = &input[start..start + error.valid_up_to()];
+                let valid = std::str::from_utf8(valid).expect("Prefix up to the error is valid");
+                (valid, Some(error.error_len()))
+            }
+        };
+        for (offset, char) in valid.char_indices() {
+            chars.push(char);
+            ends.push(start + offset + char.len_utf8());
+        }
+        start += valid.len();
+        match invalid {
+            None => break,
+            Some(Some(length)) => {
+                start += length;
+                chars.push(char::REPLACEMENT_CHARACTER);
+                ends.push(start);
+            }
+            Some(None) => {
+                *partial = input[start..].to_vec();
+                break;
+            }
+        }
+    }
+    let (taken, found) = window.advance(&chars);
+    if !found {
+        return None;
+    }
+    partial.clear();
+    Some(ends[taken - 1] - carried)
+}
+
+enum Source<'a> {
+    Bytes(Detector, &'a [u8]),
+    // The characters and how many of them were consumed.
+    Unicode(Window<UnicodeCounts>, Vec<char>, usize),
+    Empty,
+}
+
+/// Iterator over the positions that end a marker, see [`markers`].
+pub struct Markers<'a>(Source<'a>);
+
+impl Iterator for Markers<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        match &mut self.0 {
+            Source::Bytes(detector, bytes) => {
+                let consumed = detector.push_slice(bytes);
+                *bytes = &bytes[consumed.unwrap_or(bytes.len())..];
+                consumed.map(|_| detector.position())
+            }
+            Source::Unicode(window, chars, offset) => {
+                let (consumed, found) = window.advance(&chars[*offset..]);
+                *offset += consumed;
+                found.then(|| window.position())
+            }
+            Source::Empty => None,
+        }
+    }
+}
+
+/// Every position that ends a window of `length` distinct characters, in
+/// order. Positions count characters; the counting table is picked from the
+/// characters in `transmission`.
+pub fn markers(transmission: &str, length: usize) -> Markers<'_> {
+    if length == 0 {
+        return Markers(Source::Empty);
+    }
+    match Alphabet::detect(transmission) {
+        Alphabet::Lowercase | Alphabet::Bytes => Markers(Source::Bytes(Detector::new(length), transmission.as_bytes())),
+        Alphabet::Unicode => Markers(Source::Unicode(Window::new(length), transmission.chars().collect(), 0)),
+    }
+}
+
+/// Position right after the first `length` consecutive distinct characters, `None` if there is no such window.
+pub fn check_for_length(transmission: &str, length: usize) -> Option<usize> {
+    markers(transmission, length).next()
+}
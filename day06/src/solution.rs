@@ -0,0 +1,29 @@
+use std::str::FromStr;
+
+use crate::{check_for_length, Transmission};
+
+// First marker of `length` in every transmission, comma separated.
+fn solve(content: &str, length: usize) -> String {
+    let positions: Vec<String> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let transmission = Transmission::from_str(line).unwrap_or_else(|error| panic!("{}", error));
+            match check_for_length(&transmission.stream, length) {
+                Some(position) => position.to_string(),
+                None => "none".to_string(),
+            }
+        })
+        .collect();
+    positions.join(",")
+}
+
+/// End of the first start-of-packet marker of every transmission.
+pub fn solve_part1(content: &str) -> String {
+    solve(content, 4)
+}
+
+/// End of the first start-of-message marker of every transmission.
+pub fn solve_part2(content: &str) -> String {
+    solve(content, 14)
+}
@@ -0,0 +1,45 @@
+use std::io::{self, Read};
+
+use crate::marker::Detector;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Reads a transmission from `reader` in chunks and looks for the first marker
+/// of every length in `lengths` at once, calling `found(length, position)` as
+/// soon as one completes. Stops reading once all markers are found or at the
+/// end of the first line. Positions count characters, UTF-8 sequences split
+/// across reads included.
+///
+/// Returns the position of every marker, `None` for those never found.
+pub fn scan<R: Read>(
+    mut reader: R,
+    lengths: &[usize],
+    mut found: impl FnMut(usize, usize),
+) -> io::Result<Vec<Option<usize>>> {
+    let mut detectors: Vec<Detector> = lengths.iter().map(|length| Detector::new(*length)).collect();
+    let mut positions = vec![None; lengths.len()];
+    let mut buffer = vec![0; CHUNK_SIZE];
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        let line_end = buffer[..read].iter().position(|byte| *byte == b'\n' || *byte == b'\r');
+        let chunk = &buffer[..line_end.unwrap_or(read)];
+        for (index, detector) in detectors.iter_mut().enumerate() {
+            if positions[index].is_some() {
+                continue;
+            }
+            if detector.push_slice(chunk).is_some() {
+                positions[index] = Some(detector.position());
+                found(detector.length(), detector.position());
+            }
+        }
+        if line_end.is_some() || positions.iter().all(Option::is_some) {
+            break;
+        }
+    }
+    Ok(positions)
+}
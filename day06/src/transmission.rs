@@ -0,0 +1,25 @@
+use std::str::FromStr;
+
+/// One line of input: a datastream, optionally followed by the expected
+/// positions of its start-of-packet and start-of-message markers. A line
+/// that doesn't end in two numbers is all datastream, spaces included.
+#[derive(Debug, PartialEq)]
+pub struct Transmission {
+    pub stream: String,
+    pub expected: Option<[usize; 2]>,
+}
+
+impl FromStr for Transmission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Streams may contain spaces, so only two trailing numbers are expected positions.
+        let mut parts = s.rsplitn(3, ' ');
+        if let (Some(message), Some(packet), Some(stream)) = (parts.next(), parts.next(), parts.next()) {
+            if let (Ok(packet), Ok(message)) = (usize::from_str(packet), usize::from_str(message)) {
+                return Ok(Transmission { stream: stream.to_string(), expected: Some([packet, message]) });
+            }
+        }
+        Ok(Transmission { stream: s.to_string(), expected: None })
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+use std::io::{self, Read};
+
+use std::str::FromStr;
+
+use day06::{
+    check_for_length, longest_distinct_window, markers, scan, solve_part1, solve_part2, window_counts, Alphabet,
+    Detector, Transmission, UnicodeCounts, Window,
+};
+
+// Every position ending `length` distinct characters, by comparing whole windows.
+fn brute_force(transmission: &str, length: usize) -> Vec<usize> {
+    let chars: Vec<char> = transmission.chars().collect();
+    (length..=chars.len())
+        .filter(|end| chars[end - length..*end].iter().collect::<HashSet<_>>().len() == length)
+        .collect()
+}
+
+fn assert_matches_brute_force(transmission: &str) {
+    for length in 1..=transmission.chars().count() + 1 {
+        let expected = brute_force(transmission, length);
+        assert_eq!(markers(transmission, length).collect::<Vec<_>>(), expected, "length {}", length);
+        assert_eq!(check_for_length(transmission, length), expected.first().copied());
+        assert_eq!(window_counts(transmission).get(length).copied().unwrap_or(0), expected.len());
+    }
+}
+
+#[test]
+fn detects_the_cheapest_alphabet() {
+    assert_eq!(Alphabet::detect("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Alphabet::Lowercase);
+    assert_eq!(Alphabet::detect(""), Alphabet::Lowercase);
+    assert_eq!(Alphabet::detect("Hello, World!"), Alphabet::Bytes);
+    assert_eq!(Alphabet::detect("grüße"), Alphabet::Unicode);
+}
+
+#[test]
+fn lowercase_matches_puzzle_examples() {
+    let examples = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+    for (transmission, packet, message) in examples {
+        assert_eq!(check_for_length(transmission, 4), Some(packet));
+        assert_eq!(check_for_length(transmission, 14), Some(message));
+        assert_matches_brute_force(transmission);
+    }
+}
+
+#[test]
+fn bytes_cover_any_ascii() {
+    assert_eq!(check_for_length("AAAABCD", 4), Some(7));
+    assert_eq!(check_for_length("aAaA", 2), Some(2));
+    assert_eq!(check_for_length("  \t\t!!", 2), Some(3));
+    assert_matches_brute_force("The quick brown fox jumps over the lazy dog!");
+    assert_matches_brute_force("0123012345~~~ABCabc");
+}
+
+#[test]
+fn unicode_counts_characters_not_bytes() {
+    assert_eq!(check_for_length("ééàü", 3), Some(4));
+    assert_eq!(check_for_length("🎄🎄🎅🎁⭐", 3), Some(4));
+    assert_eq!(markers("ααβγαβγ", 3).collect::<Vec<_>>(), vec![4, 5, 6, 7]);
+    assert_matches_brute_force("grüße aus köln, 東京 und 🎄🎅🎄");
+
+    let mut window: Window<UnicodeCounts> = Window::new(2);
+    assert_eq!(window.advance(&['ü', 'ü', 'ß']), (3, true));
+    assert_eq!(window.position(), 3);
+}
+
+#[test]
+fn longest_window_is_a_byte_range() {
+    let transmission = "aaéüßb";
+    let range = longest_distinct_window(transmission);
+    assert_eq!(&transmission[range], "aéüßb");
+    assert_eq!(longest_distinct_window(""), 0..0);
+    assert_eq!(&"abcabcd"[longest_distinct_window("abcabcd")], "abcd");
+}
+
+#[test]
+fn detector_switches_tables_mid_stream() {
+    let mut detector = Detector::new(4);
+    assert_eq!(detector.push_slice(b"abca"), None);
+    assert_eq!(detector.alphabet(), Alphabet::Lowercase);
+    // The window `bcaB` is the first one with four different bytes.
+    assert_eq!(detector.push_slice(b"Bxyz"), Some(1));
+    assert_eq!(detector.alphabet(), Alphabet::Bytes);
+    assert_eq!(detector.position(), 5);
+
+    // Switching before the window is full keeps what was seen so far.
+    let mut detector = Detector::new(3);
+    assert!(!detector.push(b'a'));
+    assert!(!detector.push(b'A'));
+    assert!(!detector.push(b'a'));
+    assert!(detector.push(b'!'));
+    assert_eq!(detector.position(), 4);
+}
+
+#[test]
+fn scan_handles_mixed_bytes() {
+    let transmission = "aaaaBBBB-+-+xyzXYZ\nrest";
+    let positions = scan(transmission.as_bytes(), &[2, 4, 6, 20], |_, _| ()).unwrap();
+    let line = transmission.lines().next().unwrap();
+    let expected: Vec<Option<usize>> =
+        [2, 4, 6, 20].iter().map(|length| brute_force(line, *length).first().copied()).collect();
+    assert_eq!(positions, expected);
+}
+
+#[test]
+fn transmissions_may_contain_spaces() {
+    let parse = |line: &str| Transmission::from_str(line).unwrap();
+    assert_eq!(parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb 7 19").expected, Some([7, 19]));
+    let hello = |expected| Transmission { stream: "Hello, World!".to_string(), expected };
+    assert_eq!(parse("Hello, World! 4 11"), hello(Some([4, 11])));
+    assert_eq!(parse("Hello, World!"), hello(None));
+    assert_eq!(parse("grüße aus Köln 12").expected, None);
+    assert_eq!(solve_part1("Hello, World!\ngrüße aus Köln"), "7,4");
+    assert_eq!(solve_part2("Hello, World! 1 2"), "none");
+}
+
+// Hands out `step` bytes per read, splitting UTF-8 sequences across reads.
+struct Trickle<'a>(&'a [u8], usize);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let read = self.1.min(self.0.len()).min(buffer.len());
+        buffer[..read].copy_from_slice(&self.0[..read]);
+        self.0 = &self.0[read..];
+        Ok(read)
+    }
+}
+
+#[test]
+fn scan_decodes_utf8_split_across_reads() {
+    let lengths: Vec<usize> = (1..=8).collect();
+    for transmission in ["αβγδεζ", "aaαβγδεζ", "Ab!🎄🎄c🎅dé", "grüße aus köln, 東京 und 🎄🎅🎄"] {
+        let expected: Vec<Option<usize>> =
+            lengths.iter().map(|length| check_for_length(transmission, *length)).collect();
+        for step in 1..=5 {
+            let positions = scan(Trickle(transmission.as_bytes(), step), &lengths, |_, _| ()).unwrap();
+            assert_eq!(positions, expected, "{:?} in reads of {}", transmission, step);
+        }
+    }
+
+    let mut detector = Detector::new(4);
+    assert_eq!(detector.push_slice("αβγ".as_bytes()), None);
+    assert_eq!(detector.alphabet(), Alphabet::Unicode);
+    assert_eq!(detector.push_slice(&"δε".as_bytes()[..1]), None);
+    // The marker ends with the second byte of δ, the first one of this slice.
+    assert_eq!(detector.push_slice(&"δε".as_bytes()[1..]), Some(1));
+    assert_eq!(detector.position(), 4);
+
+    // Invalid sequences count as one U+FFFD each.
+    let mut detector = Detector::new(3);
+    assert_eq!(detector.push_slice(b"a\xffb\xff"), Some(3));
+    assert_eq!(detector.push_slice(b"\xffc"), Some(2));
+}
@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+use day07::{parse, FileSystem, NodeId};
+
+// A single chain of `depth` nested directories with one file in each,
+// the worst case for recomputing subtree sizes at every directory.
+fn deep_transcript(depth: usize) -> String {
+    let mut lines = vec!["$ cd /".to_string()];
+    for level in 0..depth {
+        lines.push("$ ls".to_string());
+        lines.push(format!("dir d{}", level));
+        lines.push(format!("{} f{}", level + 1, level));
+        lines.push(format!("$ cd d{}", level));
+    }
+    lines.join("\n")
+}
+
+// Reference implementation without the cache: every directory sums its own subtree.
+fn uncached_size(fs: &FileSystem, dir: NodeId) -> usize {
+    let mut size = 0;
+    let mut stack = vec![dir];
+    while let Some(id) = stack.pop() {
+        if fs.node(id).is_dir() {
+            stack.extend(fs.children(id));
+        } else {
+            size += fs.node(id).size;
+        }
+    }
+    size
+}
+
+fn uncached_size_max(fs: &FileSystem, max: usize) -> usize {
+    fs.directories()
+        .map(|id| uncached_size(fs, id))
+        .filter(|size| *size <= max)
+        .sum()
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn main() {
+    let max = 100_000;
+    println!("{:>8} {:>12} {:>12} {:>12} {:>12}", "depth", "parse", "cached", "closest", "uncached");
+    for depth in [1_000, 2_000, 4_000, 8_000] {
+        let transcript = deep_transcript(depth);
+        let (fs, parse_time) = time(|| parse(&transcript));
+        let (cached, cached_time) = time(|| fs.calc_size_max(max));
+        let (_, closest_time) = time(|| fs.get_closest_to(max));
+        let (uncached, uncached_time) = time(|| uncached_size_max(&fs, max));
+        assert_eq!(cached, uncached);
+        println!(
+            "{:>8} {:>12?} {:>12?} {:>12?} {:>12?}",
+            depth, parse_time, cached_time, closest_time, uncached_time
+        );
+    }
+}
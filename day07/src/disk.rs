@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::filesystem::{is_valid_name, FileSystem, NodeId};
+use crate::render::render_transcript;
+
+/// Name of the manifest recording file sizes when files are created empty.
+pub const SIZES_FILE: &str = ".day07-sizes";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SizeMode {
+    /// Files are created sparse with their full apparent size.
+    Sparse,
+    /// Files are created empty and their sizes go into `SIZES_FILE` at the top.
+    Sidecar,
+}
+
+/// Creates the tree below `id` under `target`, which must not exist yet.
+/// Fails without writing anything if a name in the tree could escape `target`.
+pub fn materialize(fs: &FileSystem, id: NodeId, target: &Path, mode: SizeMode) -> io::Result<()> {
+    check_names(fs, id)?;
+    fs::create_dir_all(target.parent().unwrap_or(target))?;
+    fs::create_dir(target)?;
+    let mut sizes = Vec::new();
+    materialize_children(fs, id, target, Path::new(""), mode, &mut sizes)?;
+
+    if mode == SizeMode::Sidecar {
+        let mut manifest = File::create(target.join(SIZES_FILE))?;
+        for (path, size) in sizes {
+            writeln!(manifest, "{}\t{}", size, path.display())?;
+        }
+    }
+    Ok(())
+}
+
+fn check_names(fs: &FileSystem, id: NodeId) -> io::Result<()> {
+    for child in fs.children(id) {
+        let name = &fs.node(child).name;
+        if !is_valid_name(name) {
+            let message = format!("Invalid name {:?} in {}", name, fs.path(id));
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+        check_names(fs, child)?;
+    }
+    Ok(())
+}
+
+fn materialize_children(
+    fs: &FileSystem,
+    id: NodeId,
+    target: &Path,
+    relative: &Path,
+    mode: SizeMode,
+    sizes: &mut Vec<(PathBuf, usize)>,
+) -> io::Result<()> {
+    for child in fs.children(id) {
+        let node = fs.node(child);
+        let relative = relative.join(&node.name);
+        let path = target.join(&relative);
+        if node.is_dir() {
+            fs::create_dir(&path)?;
+            materialize_children(fs, child, target, &relative, mode, sizes)?;
+            continue;
+        }
+        let file = File::create(&path)?;
+        match mode {
+            SizeMode::Sparse => file.set_len(node.size as u64)?,
+            SizeMode::Sidecar => sizes.push((relative, node.size)),
+        }
+    }
+    Ok(())
+}
+
+fn read_sizes(root: &Path) -> io::Result<HashMap<PathBuf, usize>> {
+    let manifest = match fs::read_to_string(root.join(SIZES_FILE)) {
+        Ok(manifest) => manifest,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(error) => return Err(error),
+    };
+    manifest
+        .lines()
+        .map(|line| {
+            let (size, path) = line
+                .split_once('\t')
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid line: {}", line)))?;
+            let size = size
+                .parse()
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid size: {}", size)))?;
+            Ok((PathBuf::from(path), size))
+        })
+        .collect()
+}
+
+/// Walks a real directory and writes the `$ cd`/`$ ls` transcript that would explore it.
+///
+/// File sizes come from `SIZES_FILE` when present and from the file metadata
+/// otherwise. Symlinks are skipped. Fails on names with whitespace, which the
+/// transcript format cannot express.
+pub fn transcript_from_dir(root: &Path) -> io::Result<String> {
+    let sizes = read_sizes(root)?;
+    let mut fs = FileSystem::new();
+    let top = fs.root();
+    scan_directory(root, Path::new(""), &sizes, &mut fs, top)?;
+    fs.update_sizes();
+    Ok(render_transcript(&fs, top))
+}
+
+fn scan_directory(
+    root: &Path,
+    relative: &Path,
+    sizes: &HashMap<PathBuf, usize>,
+    fs: &mut FileSystem,
+    id: NodeId,
+) -> io::Result<()> {
+    for entry in fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if relative.as_os_str().is_empty() && name == SIZES_FILE {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if metadata.file_type().is_symlink() {
+            continue;
+        }
+        // `cd` and `ls` entries split on spaces, so such names cannot be read back.
+        if name.contains(char::is_whitespace) {
+            let directory = root.join(relative);
+            let message = format!("Cannot write name {:?} in {} to a transcript", name, directory.display());
+            return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+        }
+        let path = relative.join(&name);
+        if metadata.is_dir() {
+            let child = fs.add_dir(id, &name);
+            scan_directory(root, &path, sizes, fs, child)?;
+        } else {
+            let size = sizes.get(&path).copied().unwrap_or(metadata.len() as usize);
+            fs.add_file(id, &name, size);
+        }
+    }
+    Ok(())
+}
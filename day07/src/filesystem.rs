@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+
+use crate::query::{Comparison, Filter, Query};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+#[derive(Debug)]
+pub enum NodeKind {
+    Directory { children: BTreeMap<String, NodeId> },
+    File,
+}
+
+#[derive(Debug)]
+pub struct Node {
+    pub name: String,
+    pub parent: Option<NodeId>,
+    /// File size, or the cached total size of everything below a directory.
+    pub size: usize,
+    pub kind: NodeKind,
+}
+
+impl Node {
+    pub fn is_dir(&self) -> bool {
+        matches!(self.kind, NodeKind::Directory { .. })
+    }
+}
+
+/// Whether `name` names a single entry: not empty, `.` or `..`, and without a `/`.
+pub fn is_valid_name(name: &str) -> bool {
+    !matches!(name, "" | "." | "..") && !name.contains('/')
+}
+
+/// Directory tree stored in an arena, with nodes referring to each other by `NodeId`.
+///
+/// Directory sizes are cached. Adding files marks the cache stale and
+/// `update_sizes` refreshes it in one pass over the arena.
+#[derive(Debug)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+    live: Vec<bool>,
+    dirty: bool,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem {
+    pub fn new() -> Self {
+        FileSystem {
+            nodes: vec![Node {
+                name: "/".to_string(),
+                parent: None,
+                size: 0,
+                kind: NodeKind::Directory { children: BTreeMap::new() },
+            }],
+            live: vec![true],
+            dirty: false,
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id).parent
+    }
+
+    pub fn size(&self, id: NodeId) -> usize {
+        assert!(!self.dirty, "Directory sizes are stale, call update_sizes first");
+        self.node(id).size
+    }
+
+    pub fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        match &self.node(dir).kind {
+            NodeKind::Directory { children } => children.get(name).copied(),
+            NodeKind::File => None,
+        }
+    }
+
+    /// Children of a directory in name order; empty for files.
+    pub fn children(&self, dir: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let children = match &self.node(dir).kind {
+            NodeKind::Directory { children } => Some(children.values().copied()),
+            NodeKind::File => None,
+        };
+        children.into_iter().flatten()
+    }
+
+    /// All nodes in the order they were created, starting with the root.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.nodes.len()).filter(|index| self.live[*index]).map(NodeId)
+    }
+
+    pub fn directories(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes().filter(|id| self.node(*id).is_dir())
+    }
+
+    /// Returns the existing directory `name` in `parent`, creating it if needed.
+    pub fn add_dir(&mut self, parent: NodeId, name: &str) -> NodeId {
+        if let Some(existing) = self.child(parent, name) {
+            return existing;
+        }
+        self.insert(parent, name, 0, NodeKind::Directory { children: BTreeMap::new() })
+    }
+
+    /// Adds a file to `parent`, replacing the size of an existing file with the same name.
+    pub fn add_file(&mut self, parent: NodeId, name: &str, size: usize) -> NodeId {
+        self.dirty = true;
+        if let Some(existing) = self.child(parent, name) {
+            self.nodes[existing.0].size = size;
+            return existing;
+        }
+        self.insert(parent, name, size, NodeKind::File)
+    }
+
+    fn insert(&mut self, parent: NodeId, name: &str, size: usize, kind: NodeKind) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        match &mut self.nodes[parent.0].kind {
+            NodeKind::Directory { children } => children.insert(name.to_string(), id),
+            NodeKind::File => panic!("Cannot add {} to file {}", name, self.nodes[parent.0].name),
+        };
+        self.nodes.push(Node {
+            name: name.to_string(),
+            parent: Some(parent),
+            size,
+            kind,
+        });
+        self.live.push(true);
+        id
+    }
+
+    /// Detaches a node from its parent and drops it together with everything below it.
+    pub fn remove(&mut self, id: NodeId) {
+        let parent = self.parent(id).expect("Cannot remove the root directory");
+        let name = self.nodes[id.0].name.clone();
+        if let NodeKind::Directory { children } = &mut self.nodes[parent.0].kind {
+            children.remove(&name);
+        }
+        let mut stack = vec![id];
+        while let Some(current) = stack.pop() {
+            self.live[current.0] = false;
+            stack.extend(self.children(current));
+        }
+        self.dirty = true;
+    }
+
+    /// Resolves an absolute or relative path with `.` and `..` segments.
+    pub fn resolve(&self, from: NodeId, path: &str) -> Result<NodeId, String> {
+        let mut current = if path.starts_with('/') { self.root() } else { from };
+        for segment in path.split('/') {
+            current = match segment {
+                "" | "." => current,
+                ".." => self.parent(current).ok_or("Cannot go above /".to_string())?,
+                _ if !self.node(current).is_dir() => {
+                    return Err(format!("{} is not a directory", self.node(current).name))
+                }
+                _ => self
+                    .child(current, segment)
+                    .ok_or(format!("No such file or directory: {}", path))?,
+            };
+        }
+        Ok(current)
+    }
+
+    /// Recomputes all directory sizes bottom-up.
+    ///
+    /// Children are always created after their parent, so walking the arena
+    /// backwards visits every node before its parent without recursing.
+    pub fn update_sizes(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        for node in self.nodes.iter_mut().filter(|node| node.is_dir()) {
+            node.size = 0;
+        }
+        for index in (1..self.nodes.len()).rev() {
+            if !self.live[index] {
+                continue;
+            }
+            let size = self.nodes[index].size;
+            if let Some(parent) = self.nodes[index].parent {
+                self.nodes[parent.0].size += size;
+            }
+        }
+        self.dirty = false;
+    }
+
+    /// Absolute path of a node, e.g. `/a/e`.
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.parent(current) {
+            names.push(self.node(current).name.as_str());
+            current = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// Number of directories between the root and `id`; the root has depth 0.
+    pub fn depth(&self, id: NodeId) -> usize {
+        let mut depth = 0;
+        let mut current = self.parent(id);
+        while let Some(parent) = current {
+            depth += 1;
+            current = self.parent(parent);
+        }
+        depth
+    }
+
+    /// Directories of at most `max`, i.e. `type=d size<=max`.
+    pub fn find_size_max(&self, max: usize) -> Vec<NodeId> {
+        let query = Query {
+            filters: vec![Filter::Dir(true), Filter::Size(Comparison::LessOrEqual, max)],
+            aggregate: None,
+        };
+        query.matches(self)
+    }
+
+    /// Total size of all directories of at most `max`, i.e. `type=d size<=max | sum`.
+    pub fn calc_size_max(&self, max: usize) -> usize {
+        self.find_size_max(max).iter().map(|id| self.size(*id)).sum()
+    }
+
+    /// Smallest directory that is at least `needed` big, i.e. the match of `type=d size>=needed | min`.
+    pub fn find_closest_to(&self, needed: usize) -> Option<NodeId> {
+        let query = Query {
+            filters: vec![Filter::Dir(true), Filter::Size(Comparison::GreaterOrEqual, needed)],
+            aggregate: None,
+        };
+        query.matches(self).into_iter().min_by_key(|id| self.size(*id))
+    }
+
+    pub fn get_closest_to(&self, needed: usize) -> Option<usize> {
+        self.find_closest_to(needed).map(|id| self.size(id))
+    }
+}
@@ -0,0 +1,19 @@
+mod disk;
+mod filesystem;
+mod parser;
+mod planner;
+mod query;
+mod render;
+mod shell;
+mod solution;
+mod validate;
+
+pub use disk::{materialize, transcript_from_dir, SizeMode, SIZES_FILE};
+pub use filesystem::{is_valid_name, FileSystem, Node, NodeId, NodeKind};
+pub use parser::parse;
+pub use planner::{Plan, Planner, Strategy};
+pub use query::{Aggregate, Comparison, Filter, Query, QueryResult};
+pub use render::{human_size, json_string, render_du, render_json, render_ncdu, render_transcript, render_tree};
+pub use shell::{Command, Entry, EntryParseError, Shell};
+pub use solution::{solve_part1, solve_part2, DISK_SIZE, REQUIRED_SPACE};
+pub use validate::{validate, Diagnostic, Issue};
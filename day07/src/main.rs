@@ -1,302 +1,183 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::env;
 use std::fs;
-use std::rc::Rc;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-type DirectoryRef = Rc<RefCell<Directory>>;
-
-pub trait Display {
-    fn display(&self, indent: usize) -> ();
-}
-
-pub trait CaluclateSize {
-    fn calc_size(&self) -> usize;
-    fn calc_size_max(&self, max: usize) -> usize;
-    fn get_closest_to(&self, current_closest: usize, needed: usize) -> usize;
-}
-
-struct EntryParseError {}
-
-struct Directory {
-    name: String,
-    entries: HashMap<String, Entry>,
-    parent: Option<Rc<RefCell<Directory>>>,
-}
-
-fn resolve_closest(this: usize, current_closest: usize, needed: usize) -> usize {
-    if current_closest == 0 {
-        return this;
-    }
-
-    if this < needed || this > current_closest {
-        return current_closest;
-    }
-    
-    return this;
-}
-
-impl Display for Directory {
-    fn display(&self, indent: usize) -> () {
-        println!("{:indent$}{} {} {}", "", self.name, "<dir>", self.calc_size(), indent=indent);
-        for entry in self.entries.values() {
-            entry.display(indent + 2);
-        }
-    }
-}
-
-impl CaluclateSize for Directory {
-    fn calc_size(&self) -> usize {
-        let mut dir_size = 0;
-        for entry in self.entries.values() {
-            dir_size += entry.calc_size();
-        }
-        return dir_size;
-    }
-
-    fn calc_size_max(&self, max: usize) -> usize {
-        let mut size = 0;
-        let this_size = self.calc_size();
-        if this_size <= max {
-            size += this_size;
-        }
-
-        for entry in self.entries.values() {
-            size += entry.calc_size_max(max);
-        }
-
-        return size;
-    }
-
-    fn get_closest_to(&self, current_closest: usize, needed: usize) -> usize {
-        let size = self.calc_size();
-        let mut new_closest = resolve_closest(size, current_closest, needed);
-
-        for entry in self.entries.values() {
-            new_closest = entry.get_closest_to(new_closest, needed);
-        }
-
-        return new_closest;
-    }
-}
-
-#[derive(Clone)]
-struct File {
-    name: String,
-    size: usize,
-}
-
-impl Display for File {
-    fn display(&self, indent: usize) -> () {
-        println!("{:indent$}{} {}", "", self.name, self.size, indent=indent);
-    }
-}
-
-impl CaluclateSize for File {
-    fn calc_size(&self) -> usize {
-        return self.size
-    }
-
-    fn calc_size_max(&self, _max: usize) -> usize {
-        return 0;
-    }
-
-    fn get_closest_to(&self, current_closest: usize, _needed: usize) -> usize {
-        return current_closest;
-    }
-}
-
-enum Entry {
-    Directory(DirectoryRef),
-    File(File),
-}
-
-impl Display for Entry {
-    fn display(&self, indent: usize) -> () {
-        match self {
-            Entry::Directory(dir) => dir.borrow_mut().display(indent),
-            Entry::File(file) => file.display(indent),
-        }
-    }
-}
-
-impl CaluclateSize for Entry {
-    fn calc_size(&self) -> usize {
-        return match self {
-            Entry::Directory(dir) => dir.borrow_mut().calc_size(),
-            Entry::File(file) => file.calc_size(),
-        }        
-    }
-
-    fn calc_size_max(&self, max: usize) -> usize {
-        return match self {
-            Entry::Directory(dir) => dir.borrow_mut().calc_size_max(max),
-            Entry::File(_) => 0,
-        }                
-    }
-
-    fn get_closest_to(&self, current_closest: usize, needed: usize) -> usize {
-        return match self {
-            Entry::Directory(dir) => dir.borrow().get_closest_to(current_closest, needed),
-            Entry::File(_) => current_closest,
-        }                        
-    }
-}
-
-impl FromStr for Entry {
-    type Err = EntryParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<_> = s.split(" ").collect();
-        if parts.len() != 2 {
-            return Err(EntryParseError {});
+use day07::{
+    materialize, parse, render_du, render_json, render_ncdu, render_tree, transcript_from_dir,
+    validate, FileSystem, NodeId, Planner, Query, QueryResult, SizeMode, Strategy, DISK_SIZE, REQUIRED_SPACE,
+};
+
+fn print_directories(hd: &FileSystem, directories: &[NodeId]) {
+    let mut listed: Vec<(String, usize)> = directories.iter().map(|id| (hd.path(*id), hd.size(*id))).collect();
+    listed.sort();
+    for (path, size) in listed {
+        println!("  {} {}", size, path);
+    }
+}
+
+fn part1(hd: &FileSystem) {
+    let query = Query::from_str("type=d size<=100000").expect("Invalid query");
+    let directories = query.matches(hd);
+    let total: usize = directories.iter().map(|id| hd.size(*id)).sum();
+    println!("Part 1: {}", total);
+    print_directories(hd, &directories);
+    //print!("{}", render_tree(hd, hd.root()));
+}
+
+fn part2(hd: &FileSystem) {
+    let planner = Planner::new(DISK_SIZE, REQUIRED_SPACE);
+    let free_space = DISK_SIZE - hd.size(hd.root());
+    let plan = planner
+        .plan(hd, Strategy::SmallestDirectory)
+        .expect("No directory is large enough");
+    println!("Part 2: {} {} {}", free_space, plan.needed, plan.freed);
+    print_directories(hd, &plan.directories);
+}
+
+fn find(args: &[String]) {
+    if args.len() < 2 {
+        eprintln!("Usage: day07 find <transcript> <query>");
+        eprintln!("  e.g. day07 find input.txt 'type=d size<=100000 | sum'");
+        std::process::exit(2);
+    }
+    let content = fs::read_to_string(&args[0]).expect("Cannot read file");
+    let hd = parse(&content);
+    let query = Query::from_str(&args[1..].join(" ")).unwrap_or_else(|error| {
+        eprintln!("Invalid query: {}", error);
+        std::process::exit(1);
+    });
+    match query.run(&hd) {
+        QueryResult::Nodes(nodes) => {
+            for id in nodes {
+                println!("{} {}", hd.size(id), hd.path(id));
+            }
         }
-        if parts[0] == "dir" {
-            return Ok(Entry::Directory(Rc::new(RefCell::new(Directory {
-                name: parts[1].to_string(),
-                entries: HashMap::new(),
-                parent: Option::None,
-            }))));
+        QueryResult::Value(Some(value)) => println!("{}", value),
+        QueryResult::Value(None) => println!("-"),
+    }
+}
+
+fn render(args: &[String]) {
+    if args.len() < 2 || args.len() > 3 {
+        eprintln!("Usage: day07 render <tree|du|json|ncdu> <transcript> [directory]");
+        std::process::exit(2);
+    }
+    let content = fs::read_to_string(&args[1]).expect("Cannot read file");
+    let hd = parse(&content);
+    let dir = args.get(2).map_or("/", String::as_str);
+    let id = hd.resolve(hd.root(), dir).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    });
+    let output = match args[0].as_str() {
+        "tree" => render_tree(&hd, id),
+        "du" => render_du(&hd, id),
+        "json" => render_json(&hd, id),
+        "ncdu" => render_ncdu(&hd, id),
+        format => {
+            eprintln!("Unknown format {}", format);
+            std::process::exit(2);
         }
-        return usize::from_str(parts[0]).map_or_else(
-            |_e| Err(EntryParseError {}),
-            |size| {
-                Ok(Entry::File(File {
-                    name: parts[1].to_string(),
-                    size,
-                }))
-            },
-        );
-    }
-}
-
-enum Command {
-    GoRoot,
-    Ls,
-    GoDown,
-    GoUp(String),
-}
-
-impl FromStr for Command {
-    type Err = EntryParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<_> = s.split(" ").collect();
-        let count = parts.len();
-        if count < 2 || count > 3 || parts[0] != "$" {
-            return Err(EntryParseError {});
+    };
+    print!("{}", output);
+}
+
+fn materialize_transcript(args: &[String]) {
+    let sidecar = args.iter().any(|arg| arg == "--sidecar");
+    let args: Vec<&String> = args.iter().filter(|arg| *arg != "--sidecar").collect();
+    if args.is_empty() || args.len() > 2 {
+        eprintln!("Usage: day07 materialize <transcript> [target directory] [--sidecar]");
+        std::process::exit(2);
+    }
+    let content = fs::read_to_string(args[0]).expect("Cannot read file");
+    let hd = parse(&content);
+    let target = match args.get(1) {
+        Some(target) => PathBuf::from(target),
+        None => env::temp_dir().join(format!("day07-{}", std::process::id())),
+    };
+    let mode = if sidecar { SizeMode::Sidecar } else { SizeMode::Sparse };
+    materialize(&hd, hd.root(), &target, mode).expect("Cannot materialize tree");
+    println!("{} ({} bytes)", target.display(), hd.size(hd.root()));
+}
+
+fn scan(args: &[String]) {
+    if args.len() != 1 {
+        eprintln!("Usage: day07 scan <directory>");
+        std::process::exit(2);
+    }
+    let transcript = transcript_from_dir(Path::new(&args[0])).expect("Cannot scan directory");
+    println!("{}", transcript);
+}
+
+fn check(args: &[String]) {
+    if args.len() != 1 {
+        eprintln!("Usage: day07 validate <transcript>");
+        std::process::exit(2);
+    }
+    let content = fs::read_to_string(&args[0]).expect("Cannot read file");
+    let diagnostics = validate(&content);
+    for diagnostic in &diagnostics {
+        println!("{}: {}", args[0], diagnostic);
+    }
+    if !diagnostics.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn plan(args: &[String]) {
+    let usage = || -> ! {
+        eprintln!("Usage: day07 plan <transcript> [--disk N] [--required N] [--strategy single|total|fewest]");
+        std::process::exit(2);
+    };
+    let mut planner = Planner::new(DISK_SIZE, REQUIRED_SPACE);
+    let mut strategies = Strategy::ALL.to_vec();
+    let mut transcript = None;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        let mut value = || rest.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--disk" => planner.disk = usize::from_str(value()).unwrap_or_else(|_| usage()),
+            "--required" => planner.required = usize::from_str(value()).unwrap_or_else(|_| usage()),
+            "--strategy" => {
+                strategies = vec![match value().as_str() {
+                    "single" => Strategy::SmallestDirectory,
+                    "total" => Strategy::SmallestTotal,
+                    "fewest" => Strategy::FewestDeletions,
+                    _ => usage(),
+                }]
+            }
+            _ if transcript.is_none() => transcript = Some(arg),
+            _ => usage(),
         }
-
-        // println!("{:?}", parts);
-
-        return match parts[1] {
-            "cd" => match parts[2] {
-                "/" => Ok(Command::GoRoot),
-                ".." => Ok(Command::GoDown),
-                _ => Ok(Command::GoUp(parts[2].to_string())),
-            },
-            "ls" => Ok(Command::Ls),
-            _ => Err(EntryParseError {}),
-        };
     }
-}
-
-#[derive(PartialEq)]
-enum State {
-    Command,
-    DirList,
-}
-
-fn part1(hd: &DirectoryRef) {
-    println!("Part 1: {}", hd.borrow_mut().calc_size_max(100000));
-    //hd.borrow_mut().display(0);
-}
-
-fn part2(hd: &DirectoryRef) {
-    let free_space = 70_000_000 - hd.borrow().calc_size();
-    let needed = 30_000_000 - free_space;
-    println!("Part 2: {} {} {}", free_space, needed, hd.borrow().get_closest_to(0, needed));
-}
+    let content = fs::read_to_string(transcript.unwrap_or_else(|| usage())).expect("Cannot read file");
+    let hd = parse(&content);
 
-fn parse(content: &String) -> DirectoryRef {
-    let root = Rc::new(RefCell::new(Directory {
-        name: "/".to_string(),
-        entries: HashMap::new(),
-        parent: Option::None,
-    }));
-    let mut current = Rc::clone(&root);
-    let mut state = State::Command;
-
-    for line in content.split("\n") {
-        if state == State::DirList {
-            match Entry::from_str(line) {
-                Ok(entry) => {
-                    match entry {
-                        Entry::Directory(dir) => {
-                            let mut current_mut = current.borrow_mut();
-                            dir.borrow_mut().parent = Some(current.clone());
-                            current_mut.entries.insert(
-                                dir.borrow().name.clone(),
-                                Entry::Directory(Rc::clone(&dir)),
-                            );
-                        }
-                        Entry::File(file) => {
-                            current
-                                .borrow_mut()
-                                .entries
-                                .insert(file.name.clone(), Entry::File(file));
-                        }
-                    }
-                    continue;
-                }
-                _ => state = State::Command,
+    println!("Need to free {} bytes", planner.needed(&hd));
+    for strategy in strategies {
+        match planner.plan(&hd, strategy) {
+            Some(plan) => {
+                println!("{}: free {} bytes ({})", strategy, plan.freed, plan.explanation);
+                print_directories(&hd, &plan.directories);
             }
-        }
-        match Command::from_str(line) {
-            Ok(command) => match command {
-                Command::GoRoot => continue,
-                Command::Ls => state = State::DirList,
-                Command::GoDown => {
-                    let mut current_temp: Option<DirectoryRef> = None;
-                    {
-                        if let Some(parent) = &current.borrow().parent {
-                            current_temp = Some(Rc::clone(parent));
-                        }
-                    }
-                    match current_temp {
-                        Some(new_current) => current = new_current,
-                        _ => todo!("Missing Partent"),
-                    }
-                }
-                Command::GoUp(to) => {
-                    let current_temp: Option<DirectoryRef>;
-                    {
-                        let borrowed = current.borrow();
-                        let child = borrowed.entries.get(&to);
-                        match child {
-                            Some(entry) => match entry {
-                                Entry::Directory(dir) => {
-                                    current_temp = Some(Rc::clone(dir));
-                                }
-                                _ => todo!("Trying to cd into file"),
-                            },
-                            _ => todo!("Missing folder {}", to),
-                        }
-                    }
-                    match current_temp {
-                        Some(new_current) => current = new_current,
-                        _ => (),
-                    }
-                }
-            },
-            _ => todo!("Unexpected line: {}", line),
+            None => println!("{}: impossible, this strategy cannot free enough space", strategy),
         }
     }
-
-    return root;
 }
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("find") => return find(&args[1..]),
+        Some("render") => return render(&args[1..]),
+        Some("materialize") => return materialize_transcript(&args[1..]),
+        Some("scan") => return scan(&args[1..]),
+        Some("validate") => return check(&args[1..]),
+        Some("plan") => return plan(&args[1..]),
+        _ => (),
+    }
+
     let files = vec!["sample.txt" , "input.txt"];
     for file in files {
         println!("Reading {}", file);
@@ -0,0 +1,48 @@
+use std::str::FromStr;
+
+use crate::filesystem::FileSystem;
+use crate::shell::{Command, Entry, Shell};
+
+#[derive(PartialEq)]
+enum State {
+    Command,
+    DirList,
+    Output,
+}
+
+pub fn parse(content: &str) -> FileSystem {
+    let mut shell = Shell::new();
+    let mut state = State::Command;
+
+    for (index, line) in content.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        if state == State::DirList {
+            match Entry::from_str(line) {
+                Ok(entry) => {
+                    shell.list(entry);
+                    continue;
+                }
+                _ => state = State::Command,
+            }
+        }
+        if state == State::Output && !line.starts_with("$ ") {
+            continue;
+        }
+        let command = Command::from_str(line)
+            .unwrap_or_else(|error| panic!("Line {}: {}", index + 1, error));
+        shell
+            .execute(&command)
+            .unwrap_or_else(|error| panic!("Line {}: {}", index + 1, error));
+        state = if command.lists() {
+            State::DirList
+        } else if command.prints() {
+            State::Output
+        } else {
+            State::Command
+        };
+    }
+
+    shell.into_fs()
+}
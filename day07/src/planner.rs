@@ -0,0 +1,290 @@
+use std::fmt;
+
+use crate::filesystem::{FileSystem, NodeId};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Strategy {
+    /// The single smallest directory that frees enough space.
+    SmallestDirectory,
+    /// The set of non-nested directories with the smallest total size that frees enough space.
+    SmallestTotal,
+    /// As few non-nested directories as possible, freeing the least space among those.
+    FewestDeletions,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [
+        Strategy::SmallestDirectory,
+        Strategy::SmallestTotal,
+        Strategy::FewestDeletions,
+    ];
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::SmallestDirectory => write!(f, "smallest directory"),
+            Strategy::SmallestTotal => write!(f, "smallest total"),
+            Strategy::FewestDeletions => write!(f, "fewest deletions"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Plan {
+    pub strategy: Strategy,
+    pub directories: Vec<NodeId>,
+    pub freed: usize,
+    pub needed: usize,
+    pub explanation: String,
+}
+
+/// Picks directories to delete so that `required` bytes are free on a disk of `disk` bytes.
+///
+/// No proposed directory is inside another one. The root directory is only
+/// proposed by `SmallestDirectory`, when no other directory is big enough,
+/// like the smallest directory of the puzzle's part 2. `SmallestTotal`
+/// and `FewestDeletions` give up on finding a better set after `MAX_STEPS`
+/// tries and say so.
+pub struct Planner {
+    pub disk: usize,
+    pub required: usize,
+}
+
+// How many combinations `search` tries before settling for the best one found.
+const MAX_STEPS: usize = 1 << 24;
+
+impl Planner {
+    pub fn new(disk: usize, required: usize) -> Self {
+        Planner { disk, required }
+    }
+
+    /// Bytes that still have to be freed, zero if there is enough space already.
+    pub fn needed(&self, fs: &FileSystem) -> usize {
+        let free = self.disk.saturating_sub(fs.size(fs.root()));
+        self.required.saturating_sub(free)
+    }
+
+    /// Returns `None` if even deleting every directory would not free enough space.
+    pub fn plan(&self, fs: &FileSystem, strategy: Strategy) -> Option<Plan> {
+        let needed = self.needed(fs);
+        if needed == 0 {
+            return Some(Plan {
+                strategy,
+                directories: Vec::new(),
+                freed: 0,
+                needed,
+                explanation: "there is enough free space already".to_string(),
+            });
+        }
+
+        let (directories, explanation) = match strategy {
+            Strategy::SmallestDirectory => match self.smallest_directory(fs, needed) {
+                Some(smallest) => {
+                    let explanation = format!(
+                        "{} is the smallest directory with at least {} bytes",
+                        fs.path(smallest),
+                        needed
+                    );
+                    (vec![smallest], explanation)
+                }
+                None if fs.size(fs.root()) >= needed => {
+                    let explanation = format!("only / has at least {} bytes", needed);
+                    (vec![fs.root()], explanation)
+                }
+                None => return None,
+            },
+            Strategy::SmallestTotal => self.smallest_total(fs, needed)?,
+            Strategy::FewestDeletions => self.fewest_deletions(fs, needed)?,
+        };
+
+        let freed = directories.iter().map(|id| fs.size(*id)).sum();
+        Some(Plan { strategy, directories, freed, needed, explanation })
+    }
+
+    fn smallest_directory(&self, fs: &FileSystem, needed: usize) -> Option<NodeId> {
+        fs.directories()
+            .filter(|id| *id != fs.root() && fs.size(*id) >= needed)
+            .min_by_key(|id| fs.size(*id))
+    }
+
+    fn smallest_total(&self, fs: &FileSystem, needed: usize) -> Option<(Vec<NodeId>, String)> {
+        // Only totals below the best single directory can improve on it.
+        let single = self.smallest_directory(fs, needed);
+        let dirs = self.preorder(fs);
+        // most[position] = the most the directories from `position` on can free together.
+        let mut most = vec![0; dirs.len() + 1];
+        for (position, (_, size, end)) in dirs.iter().enumerate().rev() {
+            most[position] = most[position + 1].max(size + most[*end]);
+        }
+
+        let limit = single.map(|id| fs.size(id));
+        let (best, complete) =
+            search(&dirs, needed, usize::MAX, None, limit, |position, _| most[position]);
+        let searched = searched(complete);
+
+        let Some((best, positions)) = best else {
+            let single = single?;
+            let explanation = format!(
+                "no combination of smaller directories frees {} bytes with less waste than {}{}",
+                needed,
+                fs.path(single),
+                searched
+            );
+            return Some((vec![single], explanation));
+        };
+        let mut nodes: Vec<NodeId> = positions.iter().map(|position| dirs[*position].0).collect();
+        nodes.sort();
+
+        let explanation = match single {
+            Some(single) => format!(
+                "deleting {} directories frees {} bytes, {} fewer than the smallest single directory {}{}",
+                nodes.len(),
+                best,
+                fs.size(single) - best,
+                fs.path(single),
+                searched
+            ),
+            None => format!(
+                "no single directory has {} bytes; these {} directories together waste the least{}",
+                needed,
+                nodes.len(),
+                searched
+            ),
+        };
+        Some((nodes, explanation))
+    }
+
+    // All directories below the root in pre-order, as (id, size, end of subtree).
+    fn preorder(&self, fs: &FileSystem) -> Vec<(NodeId, usize, usize)> {
+        let mut dirs: Vec<(NodeId, usize, usize)> = Vec::new();
+        let mut stack: Vec<(NodeId, bool)> = fs.children(fs.root()).map(|id| (id, false)).collect();
+        stack.reverse();
+        let mut open: Vec<usize> = Vec::new();
+        while let Some((id, done)) = stack.pop() {
+            if done {
+                let index = open.pop().expect("Unbalanced directory walk");
+                dirs[index].2 = dirs.len();
+                continue;
+            }
+            if !fs.node(id).is_dir() {
+                continue;
+            }
+            open.push(dirs.len());
+            dirs.push((id, fs.size(id), 0));
+            stack.push((id, true));
+            let mut children: Vec<_> = fs.children(id).map(|child| (child, false)).collect();
+            children.reverse();
+            stack.extend(children);
+        }
+        dirs
+    }
+
+    fn fewest_deletions(&self, fs: &FileSystem, needed: usize) -> Option<(Vec<NodeId>, String)> {
+        let dirs = self.preorder(fs);
+        // most[count][position] = the most `count` directories from `position` on can free together.
+        let mut most: Vec<Vec<usize>> = vec![vec![0; dirs.len() + 1]];
+        while most.last().expect("No counts")[0] < needed {
+            let fewer = most.last().expect("No counts");
+            let mut layer = vec![0; dirs.len() + 1];
+            for (position, (_, size, end)) in dirs.iter().enumerate().rev() {
+                layer[position] = layer[position + 1].max(size + fewer[*end]);
+            }
+            if layer[0] == fewer[0] {
+                return None;
+            }
+            most.push(layer);
+        }
+        let count = most.len() - 1;
+
+        // Start from the set that frees the most and look for one that frees less but enough.
+        let mut seed = Vec::new();
+        let mut position = 0;
+        while seed.len() < count {
+            let (_, size, end) = dirs[position];
+            if size + most[count - seed.len() - 1][end] == most[count - seed.len()][position] {
+                seed.push(position);
+                position = end;
+            } else {
+                position += 1;
+            }
+        }
+        let seed = (most[count][0], seed);
+        let (best, complete) =
+            search(&dirs, needed, count, Some(seed), None, |position, left| most[left][position]);
+        let (total, positions) = best.expect("No set of directories despite the seed");
+        let nodes: Vec<NodeId> = positions.iter().map(|position| dirs[*position].0).collect();
+
+        let searched = searched(complete);
+        let explanation = if count == 1 {
+            let path = fs.path(nodes[0]);
+            format!("{} alone frees {} bytes, the smallest single directory that is enough", path, total)
+        } else {
+            format!(
+                "fewer than {} directories cannot free {} bytes; these free the least of the sets that can{}",
+                count,
+                needed,
+                searched
+            )
+        };
+        Some((nodes, explanation))
+    }
+}
+
+// Branch and bound over `dirs` in pre-order: at every position a directory is
+// either deleted (skip to the position after its subtree) or kept (go to the
+// next position), which only ever yields non-nested sets. Finds the smallest
+// total of at most `count` directories that frees `needed`, below `limit` and
+// below the total of `best` if given. `most(position, left)` is the most that
+// `left` directories from `position` on can free. Memory grows with the number
+// of directories, not with their sizes. Returns the best (total, positions)
+// and whether every combination was tried.
+fn search(
+    dirs: &[(NodeId, usize, usize)],
+    needed: usize,
+    count: usize,
+    mut best: Option<(usize, Vec<usize>)>,
+    limit: Option<usize>,
+    most: impl Fn(usize, usize) -> usize,
+) -> (Option<(usize, Vec<usize>)>, bool) {
+    let mut chosen: Vec<usize> = Vec::new();
+    let mut stack = vec![(0, 0, 0)];
+    if best.as_ref().is_some_and(|(best, _)| *best == needed) {
+        stack.clear();
+    }
+    let mut steps = 0;
+    while let Some((position, total, deleted)) = stack.pop() {
+        chosen.truncate(deleted);
+        let limit = best.as_ref().map(|(best, _)| *best).or(limit);
+        if limit.is_some_and(|limit| total >= limit) {
+            continue;
+        }
+        if total >= needed {
+            best = Some((total, chosen.clone()));
+            if total == needed {
+                break;
+            }
+            continue;
+        }
+        if position == dirs.len() || deleted == count || total + most(position, count - deleted) < needed {
+            continue;
+        }
+        steps += 1;
+        if steps > MAX_STEPS {
+            return (best, false);
+        }
+        let (_, size, end) = dirs[position];
+        stack.push((position + 1, total, deleted));
+        chosen.push(position);
+        stack.push((end, total + size, deleted + 1));
+    }
+    (best, true)
+}
+
+fn searched(complete: bool) -> String {
+    if complete {
+        String::new()
+    } else {
+        format!(" of the {} combinations tried", MAX_STEPS)
+    }
+}
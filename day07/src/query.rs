@@ -0,0 +1,182 @@
+use std::str::FromStr;
+
+use crate::filesystem::{FileSystem, NodeId};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(&self, left: usize, right: usize) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    Name(String),
+    Dir(bool),
+    Size(Comparison, usize),
+    Depth(Comparison, usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Aggregate {
+    Sum,
+    Count,
+    Min,
+    Max,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum QueryResult {
+    Nodes(Vec<NodeId>),
+    Value(Option<usize>),
+}
+
+/// A `find`-like query: filters that all have to match, optionally followed
+/// by an aggregate over the sizes of the matches.
+///
+/// `type=d size<=100000 | sum` adds up all directories of at most 100000.
+/// Supported filters are `name=<glob>`, `type=f|d`, `size<op>N` and
+/// `depth<op>N` with `<`, `<=`, `=`, `!=`, `>=` and `>`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    pub filters: Vec<Filter>,
+    pub aggregate: Option<Aggregate>,
+}
+
+fn glob_matches(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            glob_matches(&pattern[1..], name) || (!name.is_empty() && glob_matches(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => glob_matches(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => glob_matches(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+impl Filter {
+    fn matches(&self, fs: &FileSystem, id: NodeId) -> bool {
+        let node = fs.node(id);
+        match self {
+            Filter::Name(glob) => {
+                let pattern: Vec<char> = glob.chars().collect();
+                let name: Vec<char> = node.name.chars().collect();
+                glob_matches(&pattern, &name)
+            }
+            Filter::Dir(dir) => node.is_dir() == *dir,
+            Filter::Size(comparison, size) => comparison.holds(fs.size(id), *size),
+            Filter::Depth(comparison, depth) => comparison.holds(fs.depth(id), *depth),
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let start = s
+            .find(['<', '>', '=', '!'])
+            .ok_or(format!("Missing comparison in {}", s))?;
+        let (field, rest) = s.split_at(start);
+        let (comparison, value) = [
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("!=", Comparison::NotEqual),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+            ("=", Comparison::Equal),
+        ]
+        .iter()
+        .find_map(|(op, comparison)| rest.strip_prefix(op).map(|value| (*comparison, value)))
+        .ok_or(format!("Invalid comparison in {}", s))?;
+
+        let number = || usize::from_str(value).map_err(|_| format!("Invalid number in {}", s));
+        match (field, comparison) {
+            ("name", Comparison::Equal) => Ok(Filter::Name(value.to_string())),
+            ("type", Comparison::Equal) => match value {
+                "d" | "dir" => Ok(Filter::Dir(true)),
+                "f" | "file" => Ok(Filter::Dir(false)),
+                _ => Err(format!("Unknown type {}", value)),
+            },
+            ("size", _) => Ok(Filter::Size(comparison, number()?)),
+            ("depth", _) => Ok(Filter::Depth(comparison, number()?)),
+            ("name" | "type", _) => Err(format!("{} only supports =", field)),
+            _ => Err(format!("Unknown field {}", field)),
+        }
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(Aggregate::Sum),
+            "count" => Ok(Aggregate::Count),
+            "min" => Ok(Aggregate::Min),
+            "max" => Ok(Aggregate::Max),
+            _ => Err(format!("Unknown aggregate {}", s)),
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (filters, aggregate) = match s.split_once('|') {
+            Some((filters, aggregate)) => (filters, Some(Aggregate::from_str(aggregate.trim())?)),
+            None => (s, None),
+        };
+        let filters = filters
+            .split_whitespace()
+            .map(Filter::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(Query { filters, aggregate })
+    }
+}
+
+impl Query {
+    pub fn matches(&self, fs: &FileSystem) -> Vec<NodeId> {
+        fs.nodes()
+            .filter(|id| self.filters.iter().all(|filter| filter.matches(fs, *id)))
+            .collect()
+    }
+
+    pub fn run(&self, fs: &FileSystem) -> QueryResult {
+        let matches = self.matches(fs);
+        let sizes = matches.iter().map(|id| fs.size(*id));
+        match self.aggregate {
+            None => QueryResult::Nodes(matches),
+            Some(Aggregate::Sum) => QueryResult::Value(Some(sizes.sum())),
+            Some(Aggregate::Count) => QueryResult::Value(Some(matches.len())),
+            Some(Aggregate::Min) => QueryResult::Value(sizes.min()),
+            Some(Aggregate::Max) => QueryResult::Value(sizes.max()),
+        }
+    }
+
+    /// Runs an aggregating query and returns its value.
+    pub fn value(&self, fs: &FileSystem) -> Option<usize> {
+        match self.run(fs) {
+            QueryResult::Value(value) => value,
+            QueryResult::Nodes(_) => panic!("Query has no aggregate"),
+        }
+    }
+}
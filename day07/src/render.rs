@@ -0,0 +1,208 @@
+use std::fmt::Write;
+
+use crate::filesystem::{FileSystem, NodeId};
+
+/// Formats a byte count the way `du -h` does: `584`, `2.5K`, `14M`.
+pub fn human_size(size: usize) -> String {
+    const UNITS: [&str; 5] = ["K", "M", "G", "T", "P"];
+    if size < 1024 {
+        return size.to_string();
+    }
+    let mut value = size as f64;
+    let mut unit = "";
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    if value < 10.0 {
+        format!("{:.1}{}", value, unit)
+    } else {
+        format!("{:.0}{}", value, unit)
+    }
+}
+
+/// `tree`-style listing with box-drawing connectors, children sorted by name.
+pub fn render_tree(fs: &FileSystem, id: NodeId) -> String {
+    let mut out = String::new();
+    writeln!(out, "{} ({})", fs.node(id).name, fs.size(id)).unwrap();
+    // (node, whether it is the last of its siblings, depth), next one on top.
+    let mut stack: Vec<(NodeId, bool, usize)> = Vec::new();
+    push_children(fs, id, 0, &mut stack);
+    // The prefix of the current line, and where it ends for each depth.
+    let mut prefix = String::new();
+    let mut ends = vec![0];
+    while let Some((child, last, depth)) = stack.pop() {
+        ends.truncate(depth + 1);
+        prefix.truncate(ends[depth]);
+        let (connector, indent) = if last { ("└── ", "    ") } else { ("├── ", "│   ") };
+        writeln!(out, "{}{}{} ({})", prefix, connector, fs.node(child).name, fs.size(child)).unwrap();
+        prefix.push_str(indent);
+        ends.push(prefix.len());
+        push_children(fs, child, depth + 1, &mut stack);
+    }
+    out
+}
+
+fn push_children(fs: &FileSystem, id: NodeId, depth: usize, stack: &mut Vec<(NodeId, bool, usize)>) {
+    let children: Vec<NodeId> = fs.children(id).collect();
+    for (index, child) in children.iter().enumerate().rev() {
+        stack.push((*child, index + 1 == children.len(), depth));
+    }
+}
+
+/// `du -h`-style listing: every directory after its subdirectories, sorted by name.
+pub fn render_du(fs: &FileSystem, id: NodeId) -> String {
+    let mut out = String::new();
+    // (directory, depth, whether its subdirectories are done), next one on top.
+    let mut stack = vec![(id, 0, false)];
+    // The path of the current directory, and where it ends for each depth.
+    let mut path = String::from(".");
+    let mut ends = Vec::new();
+    while let Some((dir, depth, done)) = stack.pop() {
+        if done {
+            path.truncate(ends[depth]);
+            writeln!(out, "{}\t{}", human_size(fs.size(dir)), path).unwrap();
+            continue;
+        }
+        if depth > 0 {
+            path.truncate(ends[depth - 1]);
+            write!(path, "/{}", fs.node(dir).name).unwrap();
+        }
+        ends.truncate(depth);
+        ends.push(path.len());
+        stack.push((dir, depth, true));
+        let children: Vec<NodeId> = fs.children(dir).filter(|child| fs.node(*child).is_dir()).collect();
+        stack.extend(children.into_iter().rev().map(|child| (child, depth + 1, false)));
+    }
+    out
+}
+
+/// `s` as a quoted JSON string.
+pub fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// JSON export of the hierarchy: `{"name", "type", "size", "children"}` per directory.
+pub fn render_json(fs: &FileSystem, id: NodeId) -> String {
+    let mut out = String::new();
+    // What is left to write, next one on top: a node or the end of a directory's
+    // children, each with its indentation and what follows it.
+    enum Step {
+        Node(NodeId, usize, &'static str),
+        Close(usize, &'static str),
+    }
+    let mut stack = vec![Step::Node(id, 0, "\n")];
+    while let Some(step) = stack.pop() {
+        let (id, indent, after) = match step {
+            Step::Node(id, indent, after) => (id, indent, after),
+            Step::Close(indent, after) => {
+                write!(out, "{:indent$}]}}{}", "", after, indent = indent).unwrap();
+                continue;
+            }
+        };
+        let node = fs.node(id);
+        let kind = if node.is_dir() { "dir" } else { "file" };
+        write!(
+            out,
+            "{:indent$}{{\"name\": {}, \"type\": \"{}\", \"size\": {}",
+            "",
+            json_string(&node.name),
+            kind,
+            fs.size(id),
+            indent = indent
+        )
+        .unwrap();
+        let children: Vec<NodeId> = fs.children(id).collect();
+        if !node.is_dir() {
+            write!(out, "}}{}", after).unwrap();
+        } else if children.is_empty() {
+            write!(out, ", \"children\": []}}{}", after).unwrap();
+        } else {
+            out.push_str(", \"children\": [\n");
+            stack.push(Step::Close(indent, after));
+            for (index, child) in children.iter().enumerate().rev() {
+                let after = if index + 1 == children.len() { "\n" } else { ",\n" };
+                stack.push(Step::Node(*child, indent + 2, after));
+            }
+        }
+    }
+    out
+}
+
+/// `ncdu`-like view of one directory: children by descending size with a share bar.
+pub fn render_ncdu(fs: &FileSystem, id: NodeId) -> String {
+    const BAR_WIDTH: usize = 10;
+    let total = fs.size(id);
+    let mut children: Vec<NodeId> = fs.children(id).collect();
+    children.sort_by(|a, b| {
+        fs.size(*b)
+            .cmp(&fs.size(*a))
+            .then_with(|| fs.node(*a).name.cmp(&fs.node(*b).name))
+    });
+
+    let mut out = String::new();
+    writeln!(out, "--- {} ({} total) ---", fs.node(id).name, human_size(total)).unwrap();
+    for child in children {
+        let size = fs.size(child);
+        let share = if total == 0 { 0.0 } else { size as f64 / total as f64 };
+        let filled = (share * BAR_WIDTH as f64).round() as usize;
+        let node = fs.node(child);
+        writeln!(
+            out,
+            "{:>7} [{}{}] {:5.1}% {}{}",
+            human_size(size),
+            "#".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled),
+            share * 100.0,
+            if node.is_dir() { "/" } else { " " },
+            node.name
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// The `$ cd`/`$ ls` transcript that explores the tree below `id` in name order.
+pub fn render_transcript(fs: &FileSystem, id: NodeId) -> String {
+    let mut lines = vec![format!("$ cd {}", fs.path(id))];
+    // Directories left to explore, next one on top; `None` goes back up.
+    let mut stack = vec![Some(id)];
+    while let Some(step) = stack.pop() {
+        let Some(dir) = step else {
+            lines.push("$ cd ..".to_string());
+            continue;
+        };
+        if dir != id {
+            lines.push(format!("$ cd {}", fs.node(dir).name));
+            stack.push(None);
+        }
+        lines.push("$ ls".to_string());
+        for child in fs.children(dir) {
+            let node = fs.node(child);
+            if node.is_dir() {
+                lines.push(format!("dir {}", node.name));
+            } else {
+                lines.push(format!("{} {}", node.size, node.name));
+            }
+        }
+        let subdirectories: Vec<NodeId> = fs.children(dir).filter(|child| fs.node(*child).is_dir()).collect();
+        stack.extend(subdirectories.into_iter().rev().map(Some));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,255 @@
+use std::str::FromStr;
+
+use crate::filesystem::{is_valid_name, FileSystem, NodeId};
+
+pub struct EntryParseError {}
+
+/// One line of `ls` output.
+pub enum Entry {
+    Directory(String),
+    File(String, usize),
+}
+
+impl FromStr for Entry {
+    type Err = EntryParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split(' ').collect();
+        if parts.len() != 2 {
+            return Err(EntryParseError {});
+        }
+        if parts[0] == "dir" {
+            return Ok(Entry::Directory(parts[1].to_string()));
+        }
+        usize::from_str(parts[0]).map_or_else(
+            |_e| Err(EntryParseError {}),
+            |size| Ok(Entry::File(parts[1].to_string(), size)),
+        )
+    }
+}
+
+pub enum Command {
+    Cd(String),
+    Ls,
+    Pwd,
+    Mkdir { parents: bool, paths: Vec<String> },
+    Touch(Vec<String>),
+    Rm { recursive: bool, paths: Vec<String> },
+    Du(Vec<String>),
+}
+
+impl Command {
+    /// Whether the lines following the command are `ls` entries.
+    pub fn lists(&self) -> bool {
+        matches!(self, Command::Ls)
+    }
+
+    /// Whether the command prints output that does not affect the file system.
+    pub fn prints(&self) -> bool {
+        matches!(self, Command::Pwd | Command::Du(_))
+    }
+}
+
+fn split_flags(args: &[&str]) -> (Vec<char>, Vec<String>) {
+    let mut flags = Vec::new();
+    let mut paths = Vec::new();
+    for arg in args {
+        match arg.strip_prefix('-') {
+            Some(chars) if !chars.is_empty() => flags.extend(chars.chars()),
+            _ => paths.push(arg.to_string()),
+        }
+    }
+    (flags, paths)
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split_whitespace().collect();
+        if parts.len() < 2 || parts[0] != "$" {
+            return Err(format!("Not a command: {}", s));
+        }
+        let (flags, paths) = split_flags(&parts[2..]);
+        let unknown = |allowed: &str| flags.iter().find(|flag| !allowed.contains(**flag)).copied();
+        if let Some(flag) = unknown(match parts[1] {
+            "mkdir" => "p",
+            "rm" => "rf",
+            "du" => "sh",
+            _ => "",
+        }) {
+            return Err(format!("Unknown flag -{} for {}", flag, parts[1]));
+        }
+
+        match (parts[1], paths.len()) {
+            ("cd", 0) => Ok(Command::Cd("/".to_string())),
+            ("cd", 1) => Ok(Command::Cd(paths[0].clone())),
+            ("ls", 0) => Ok(Command::Ls),
+            ("pwd", 0) => Ok(Command::Pwd),
+            ("mkdir", 1..) => Ok(Command::Mkdir { parents: flags.contains(&'p'), paths }),
+            ("touch", 1..) => Ok(Command::Touch(paths)),
+            ("rm", 1..) => Ok(Command::Rm { recursive: flags.contains(&'r'), paths }),
+            ("du", _) => Ok(Command::Du(paths)),
+            ("cd" | "ls" | "pwd" | "mkdir" | "touch" | "rm", _) => {
+                Err(format!("Wrong number of arguments: {}", s))
+            }
+            _ => Err(format!("Unknown command: {}", parts[1])),
+        }
+    }
+}
+
+fn split_path(path: &str) -> (&str, &str) {
+    let path = path.trim_end_matches('/');
+    match path.rsplit_once('/') {
+        Some(("", name)) => ("/", name),
+        Some((parent, name)) => (parent, name),
+        None => (".", path),
+    }
+}
+
+// The parent directory and name of a new entry at `path`.
+fn split_new_path(path: &str) -> Result<(&str, &str), String> {
+    let (parent, name) = split_path(path);
+    if !is_valid_name(name) {
+        return Err(format!("Invalid name: {}", path));
+    }
+    Ok((parent, name))
+}
+
+/// Replays transcript commands against a `FileSystem`, tracking the working directory.
+pub struct Shell {
+    fs: FileSystem,
+    cwd: NodeId,
+}
+
+impl Default for Shell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shell {
+    pub fn new() -> Self {
+        let fs = FileSystem::new();
+        let cwd = fs.root();
+        Shell { fs, cwd }
+    }
+
+    pub fn cwd(&self) -> NodeId {
+        self.cwd
+    }
+
+    /// The file system, with directory sizes brought up to date first.
+    pub fn fs(&mut self) -> &FileSystem {
+        self.fs.update_sizes();
+        &self.fs
+    }
+
+    // Without refreshing directory sizes, for lookups between listings.
+    pub(crate) fn fs_mut(&mut self) -> &mut FileSystem {
+        &mut self.fs
+    }
+
+    pub(crate) fn set_cwd(&mut self, cwd: NodeId) {
+        self.cwd = cwd;
+    }
+
+    pub fn into_fs(mut self) -> FileSystem {
+        self.fs.update_sizes();
+        self.fs
+    }
+
+    /// Records an `ls` output line for the working directory.
+    pub fn list(&mut self, entry: Entry) -> NodeId {
+        match entry {
+            Entry::Directory(name) => self.fs.add_dir(self.cwd, &name),
+            Entry::File(name, size) => self.fs.add_file(self.cwd, &name, size),
+        }
+    }
+
+    pub fn execute(&mut self, command: &Command) -> Result<(), String> {
+        match command {
+            Command::Cd(path) => {
+                let target = self.fs.resolve(self.cwd, path)?;
+                if !self.fs.node(target).is_dir() {
+                    return Err(format!("Not a directory: {}", path));
+                }
+                self.cwd = target;
+            }
+            Command::Ls | Command::Pwd => (),
+            Command::Du(paths) => {
+                for path in paths {
+                    self.fs.resolve(self.cwd, path)?;
+                }
+            }
+            Command::Mkdir { parents, paths } => {
+                for path in paths {
+                    self.mkdir(path, *parents)?;
+                }
+            }
+            Command::Touch(paths) => {
+                for path in paths {
+                    let (parent, name) = split_new_path(path)?;
+                    let dir = self.directory(parent)?;
+                    if self.fs.child(dir, name).is_none() {
+                        self.fs.add_file(dir, name, 0);
+                    }
+                }
+            }
+            Command::Rm { recursive, paths } => {
+                for path in paths {
+                    let target = self.fs.resolve(self.cwd, path)?;
+                    if target == self.fs.root() {
+                        return Err("Refusing to remove /".to_string());
+                    }
+                    if self.fs.node(target).is_dir() && !recursive {
+                        return Err(format!("Is a directory: {}", path));
+                    }
+                    let mut current = Some(self.cwd);
+                    while let Some(id) = current {
+                        if id == target {
+                            return Err(format!("Cannot remove the working directory: {}", path));
+                        }
+                        current = self.fs.parent(id);
+                    }
+                    self.fs.remove(target);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn directory(&self, path: &str) -> Result<NodeId, String> {
+        let dir = self.fs.resolve(self.cwd, path)?;
+        if !self.fs.node(dir).is_dir() {
+            return Err(format!("Not a directory: {}", path));
+        }
+        Ok(dir)
+    }
+
+    fn mkdir(&mut self, path: &str, parents: bool) -> Result<(), String> {
+        if !parents {
+            let (parent, name) = split_new_path(path)?;
+            let dir = self.directory(parent)?;
+            if self.fs.child(dir, name).is_some() {
+                return Err(format!("File exists: {}", path));
+            }
+            self.fs.add_dir(dir, name);
+            return Ok(());
+        }
+
+        let mut current = if path.starts_with('/') { self.fs.root() } else { self.cwd };
+        for segment in path.split('/') {
+            current = match segment {
+                "" | "." => current,
+                ".." => self.fs.parent(current).ok_or("Cannot go above /".to_string())?,
+                _ => match self.fs.child(current, segment) {
+                    Some(child) if self.fs.node(child).is_dir() => child,
+                    Some(_) => return Err(format!("Not a directory: {}", segment)),
+                    None => self.fs.add_dir(current, segment),
+                },
+            };
+        }
+        Ok(())
+    }
+}
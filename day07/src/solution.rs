@@ -0,0 +1,18 @@
+use crate::{parse, Planner, Strategy};
+
+pub const DISK_SIZE: usize = 70_000_000;
+pub const REQUIRED_SPACE: usize = 30_000_000;
+
+/// Total size of the directories of at most 100000.
+pub fn solve_part1(content: &str) -> String {
+    parse(content).calc_size_max(100_000).to_string()
+}
+
+/// Size of the smallest directory that frees enough space for the update.
+pub fn solve_part2(content: &str) -> String {
+    let hd = parse(content);
+    let plan = Planner::new(DISK_SIZE, REQUIRED_SPACE)
+        .plan(&hd, Strategy::SmallestDirectory)
+        .expect("No directory is large enough");
+    plan.freed.to_string()
+}
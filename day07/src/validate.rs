@@ -0,0 +1,227 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::filesystem::{is_valid_name, NodeId};
+use crate::shell::{Command, Entry, Shell};
+
+#[derive(Debug, PartialEq)]
+pub enum Issue {
+    /// An entry was listed again with a different size or type.
+    ConflictingListing { name: String, previous: String, current: String, previous_line: usize },
+    /// `cd` into a directory that no listing mentioned.
+    UnlistedDirectory { name: String },
+    /// `cd ..` while already at `/`.
+    AboveRoot,
+    /// A directory that was never `ls`ed, so its size is unknown.
+    NeverVisited { path: String },
+    /// An entry whose name is empty, `.`, `..` or contains a `/`.
+    InvalidName { name: String },
+    /// A file listed with a size of zero or less.
+    InvalidSize { name: String, size: String },
+    /// Anything the shell could not run.
+    Error(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub issue: Issue,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.issue {
+            Issue::ConflictingListing { name, previous, current, previous_line } => write!(
+                f,
+                "{} listed as {} but was {} on line {}",
+                name, current, previous, previous_line
+            ),
+            Issue::UnlistedDirectory { name } => write!(f, "cd into {}, which was never listed", name),
+            Issue::AboveRoot => write!(f, "cd .. above /"),
+            Issue::NeverVisited { path } => write!(f, "directory {} was never visited with ls, size unknown", path),
+            Issue::InvalidName { name } => write!(f, "invalid name {:?}", name),
+            Issue::InvalidSize { name, size } => write!(f, "file {} has invalid size {}", name, size),
+            Issue::Error(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum State {
+    Command,
+    DirList,
+    Output,
+}
+
+struct Validator {
+    shell: Shell,
+    diagnostics: Vec<Diagnostic>,
+    // Line on which each entry was first listed.
+    listed: HashMap<NodeId, usize>,
+    visited: HashSet<NodeId>,
+}
+
+fn describe(is_dir: bool, size: usize) -> String {
+    if is_dir {
+        "a directory".to_string()
+    } else {
+        format!("size {}", size)
+    }
+}
+
+impl Validator {
+    fn report(&mut self, line: usize, issue: Issue) {
+        self.diagnostics.push(Diagnostic { line, issue });
+    }
+
+    fn list(&mut self, line: usize, entry: Entry) {
+        let cwd = self.shell.cwd();
+        let (name, is_dir, size) = match &entry {
+            Entry::Directory(name) => (name.clone(), true, 0),
+            Entry::File(name, size) => (name.clone(), false, *size),
+        };
+        if !is_valid_name(&name) {
+            self.report(line, Issue::InvalidName { name });
+            return;
+        }
+        if size == 0 && !is_dir {
+            self.report(line, Issue::InvalidSize { name: name.clone(), size: "0".to_string() });
+        }
+
+        let existing = self.shell.fs_mut().child(cwd, &name);
+        if let Some(existing) = existing {
+            let node = self.shell.fs_mut().node(existing);
+            let (was_dir, was_size) = (node.is_dir(), node.size);
+            if was_dir != is_dir || (!is_dir && was_size != size) {
+                let previous_line = self.listed.get(&existing).copied().unwrap_or(0);
+                let issue = Issue::ConflictingListing {
+                    name,
+                    previous: describe(was_dir, was_size),
+                    current: describe(is_dir, size),
+                    previous_line,
+                };
+                self.report(line, issue);
+            }
+            if was_dir != is_dir {
+                return;
+            }
+        }
+
+        let id = self.shell.list(entry);
+        self.listed.entry(id).or_insert(line);
+    }
+
+    fn cd(&mut self, line: usize, path: &str) {
+        let mut current = if path.starts_with('/') { self.shell.fs_mut().root() } else { self.shell.cwd() };
+        for segment in path.split('/') {
+            let fs = self.shell.fs_mut();
+            current = match segment {
+                "" | "." => current,
+                ".." => match fs.parent(current) {
+                    Some(parent) => parent,
+                    None => {
+                        self.report(line, Issue::AboveRoot);
+                        current
+                    }
+                },
+                _ => match fs.child(current, segment) {
+                    Some(child) if fs.node(child).is_dir() => child,
+                    Some(_) => {
+                        self.report(line, Issue::Error(format!("cd into file {}", segment)));
+                        return;
+                    }
+                    None => {
+                        let child = fs.add_dir(current, segment);
+                        self.listed.insert(child, line);
+                        self.report(line, Issue::UnlistedDirectory { name: segment.to_string() });
+                        child
+                    }
+                },
+            };
+        }
+        self.shell.set_cwd(current);
+    }
+}
+
+/// Replays a transcript without panicking and reports everything suspicious in it.
+pub fn validate(content: &str) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        shell: Shell::new(),
+        diagnostics: Vec::new(),
+        listed: HashMap::new(),
+        visited: HashSet::new(),
+    };
+    let root = validator.shell.cwd();
+    validator.listed.insert(root, 1);
+    let mut state = State::Command;
+
+    for (index, line) in content.lines().enumerate() {
+        let number = index + 1;
+        if line.is_empty() {
+            continue;
+        }
+        if state == State::DirList {
+            if let Ok(entry) = Entry::from_str(line) {
+                validator.list(number, entry);
+                continue;
+            }
+            match line.split_once(' ').map(|(size, name)| (i64::from_str(size), size, name)) {
+                Some((Ok(parsed), size, name)) if parsed <= 0 => {
+                    let issue = Issue::InvalidSize { name: name.to_string(), size: size.to_string() };
+                    validator.report(number, issue);
+                    continue;
+                }
+                Some((Ok(_), _, _)) => {
+                    validator.report(number, Issue::Error(format!("malformed ls entry {:?}", line)));
+                    continue;
+                }
+                _ => state = State::Command,
+            }
+        }
+        if state == State::Output && !line.starts_with("$ ") {
+            continue;
+        }
+
+        state = State::Command;
+        let command = match Command::from_str(line) {
+            Ok(command) => command,
+            Err(error) => {
+                validator.report(number, Issue::Error(error));
+                continue;
+            }
+        };
+        match &command {
+            Command::Cd(path) => validator.cd(number, path),
+            Command::Ls => {
+                validator.visited.insert(validator.shell.cwd());
+                state = State::DirList;
+            }
+            _ => {
+                if let Err(error) = validator.shell.execute(&command) {
+                    validator.report(number, Issue::Error(error));
+                }
+                if command.prints() {
+                    state = State::Output;
+                }
+            }
+        }
+    }
+
+    let fs = validator.shell.fs();
+    let mut never_visited: Vec<Diagnostic> = fs
+        .directories()
+        .filter(|id| !validator.visited.contains(id))
+        .filter_map(|id| {
+            validator.listed.get(&id).map(|line| Diagnostic {
+                line: *line,
+                issue: Issue::NeverVisited { path: fs.path(id) },
+            })
+        })
+        .collect();
+    let mut diagnostics = validator.diagnostics;
+    diagnostics.append(&mut never_visited);
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+    diagnostics
+}
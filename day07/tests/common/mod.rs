@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use day07::{FileSystem, NodeId};
+use proptest::prelude::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tree {
+    File(usize),
+    Dir(BTreeMap<String, Tree>),
+}
+
+const NAME: &str = "[a-z]{1,4}(\\.[a-z]{1,3})?";
+
+/// Files of `sizes` bytes in directories up to `depth` deep with fewer than `entries` entries each.
+pub fn tree(sizes: Range<usize>, depth: u32, entries: usize) -> impl Strategy<Value = Tree> {
+    let leaf = sizes.prop_map(Tree::File);
+    leaf.prop_recursive(depth, depth * entries as u32 * 2, entries as u32, move |inner| {
+        prop::collection::btree_map(NAME, inner, 0..entries).prop_map(Tree::Dir)
+    })
+}
+
+/// A root directory with `top` entries, each a `tree`.
+pub fn root(sizes: Range<usize>, depth: u32, entries: usize, top: Range<usize>) -> impl Strategy<Value = Tree> {
+    prop::collection::btree_map(NAME, tree(sizes, depth, entries), top).prop_map(Tree::Dir)
+}
+
+fn build(tree: &Tree, fs: &mut FileSystem, dir: NodeId) {
+    let Tree::Dir(entries) = tree else { return };
+    for (name, entry) in entries {
+        match entry {
+            Tree::File(size) => {
+                fs.add_file(dir, name, *size);
+            }
+            Tree::Dir(_) => {
+                let child = fs.add_dir(dir, name);
+                build(entry, fs, child);
+            }
+        }
+    }
+}
+
+pub fn filesystem(tree: &Tree) -> FileSystem {
+    let mut fs = FileSystem::new();
+    let root = fs.root();
+    build(tree, &mut fs, root);
+    fs.update_sizes();
+    fs
+}
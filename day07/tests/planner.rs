@@ -0,0 +1,101 @@
+use day07::{FileSystem, NodeId, Planner};
+use proptest::prelude::*;
+
+mod common;
+
+use common::{filesystem, root, Tree};
+
+// Few, small directories so every set of them can be tried.
+const MAX_DIRECTORIES: usize = 12;
+
+fn tree() -> impl Strategy<Value = Tree> {
+    root(1..50, 3, 4, 1..5)
+}
+
+// Needs `permille` of everything on the disk freed, at least one byte.
+fn needed(fs: &FileSystem, permille: usize) -> usize {
+    (fs.size(fs.root()) * permille / 1000).max(1)
+}
+
+fn is_inside(fs: &FileSystem, id: NodeId, ancestor: NodeId) -> bool {
+    let mut current = fs.parent(id);
+    while let Some(dir) = current {
+        if dir == ancestor {
+            return true;
+        }
+        current = fs.parent(dir);
+    }
+    false
+}
+
+// Every set of non-nested directories below the root that frees `needed`, as (count, total).
+fn brute_force(fs: &FileSystem, needed: usize) -> Vec<(usize, usize)> {
+    let dirs: Vec<NodeId> = fs.directories().filter(|id| *id != fs.root()).collect();
+    let mut sets = Vec::new();
+    for mask in 1usize..1 << dirs.len() {
+        let chosen: Vec<NodeId> = (0..dirs.len()).filter(|bit| mask & 1 << bit != 0).map(|bit| dirs[bit]).collect();
+        if chosen.iter().any(|a| chosen.iter().any(|b| is_inside(fs, *a, *b))) {
+            continue;
+        }
+        let total: usize = chosen.iter().map(|id| fs.size(*id)).sum();
+        if total >= needed {
+            sets.push((chosen.len(), total));
+        }
+    }
+    sets
+}
+
+// A plan frees what it claims, at least `needed`, without nested directories.
+fn check_plan(fs: &FileSystem, directories: &[NodeId], freed: usize, needed: usize) -> Result<(), TestCaseError> {
+    prop_assert_eq!(directories.iter().map(|id| fs.size(*id)).sum::<usize>(), freed);
+    prop_assert!(freed >= needed);
+    for a in directories {
+        prop_assert!(fs.node(*a).is_dir());
+        for b in directories {
+            prop_assert!(!is_inside(fs, *a, *b));
+        }
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn smallest_directory_matches_brute_force(tree in tree(), permille in 1usize..=1000) {
+        let fs = filesystem(&tree);
+        let needed = needed(&fs, permille);
+        let planner = Planner::new(fs.size(fs.root()), needed);
+        let expected = fs.directories().map(|id| fs.size(id)).filter(|size| *size >= needed).min();
+        let plan = planner.plan(&fs, day07::Strategy::SmallestDirectory);
+        prop_assert_eq!(plan.as_ref().map(|plan| plan.freed), expected);
+        prop_assert_eq!(plan.map(|plan| plan.freed), fs.get_closest_to(needed));
+    }
+
+    #[test]
+    fn smallest_total_matches_brute_force(tree in tree(), permille in 1usize..=1000) {
+        let fs = filesystem(&tree);
+        let needed = needed(&fs, permille);
+        prop_assume!(fs.directories().count() <= MAX_DIRECTORIES + 1);
+        let planner = Planner::new(fs.size(fs.root()), needed);
+        let expected = brute_force(&fs, needed).into_iter().map(|(_, total)| total).min();
+        let plan = planner.plan(&fs, day07::Strategy::SmallestTotal);
+        prop_assert_eq!(plan.as_ref().map(|plan| plan.freed), expected);
+        if let Some(plan) = plan {
+            check_plan(&fs, &plan.directories, plan.freed, needed)?;
+        }
+    }
+
+    #[test]
+    fn fewest_deletions_matches_brute_force(tree in tree(), permille in 1usize..=1000) {
+        let fs = filesystem(&tree);
+        let needed = needed(&fs, permille);
+        prop_assume!(fs.directories().count() <= MAX_DIRECTORIES + 1);
+        let planner = Planner::new(fs.size(fs.root()), needed);
+        // Fewest directories first, then the least freed.
+        let expected = brute_force(&fs, needed).into_iter().min();
+        let plan = planner.plan(&fs, day07::Strategy::FewestDeletions);
+        prop_assert_eq!(plan.as_ref().map(|plan| (plan.directories.len(), plan.freed)), expected);
+        if let Some(plan) = plan {
+            check_plan(&fs, &plan.directories, plan.freed, needed)?;
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use day07::{parse, render_transcript, FileSystem, NodeId};
+use proptest::prelude::*;
+
+mod common;
+
+use common::{filesystem, root, Tree};
+
+fn tree() -> impl Strategy<Value = Tree> {
+    root(1..300_000, 6, 6, 0..8)
+}
+
+fn transcript(tree: &Tree) -> String {
+    let fs = filesystem(tree);
+    render_transcript(&fs, fs.root())
+}
+
+fn from_fs(fs: &FileSystem, id: NodeId) -> Tree {
+    if !fs.node(id).is_dir() {
+        return Tree::File(fs.size(id));
+    }
+    Tree::Dir(
+        fs.children(id)
+            .map(|child| (fs.node(child).name.clone(), from_fs(fs, child)))
+            .collect(),
+    )
+}
+
+fn total(tree: &Tree) -> usize {
+    match tree {
+        Tree::File(size) => *size,
+        Tree::Dir(entries) => entries.values().map(total).sum(),
+    }
+}
+
+fn brute_force_size_max(tree: &Tree, max: usize) -> usize {
+    let Tree::Dir(entries) = tree else { return 0 };
+    let own = total(tree);
+    let below: usize = entries.values().map(|entry| brute_force_size_max(entry, max)).sum();
+    below + if own <= max { own } else { 0 }
+}
+
+proptest! {
+    #[test]
+    fn total_size_is_sum_of_files(tree in tree()) {
+        let fs = parse(&transcript(&tree));
+        prop_assert_eq!(fs.size(fs.root()), total(&tree));
+    }
+
+    #[test]
+    fn directories_are_at_least_as_big_as_their_children(tree in tree()) {
+        let fs = parse(&transcript(&tree));
+        for dir in fs.directories() {
+            let children: Vec<NodeId> = fs.children(dir).collect();
+            for child in &children {
+                prop_assert!(fs.size(dir) >= fs.size(*child));
+            }
+            prop_assert_eq!(fs.size(dir), children.iter().map(|child| fs.size(*child)).sum::<usize>());
+        }
+    }
+
+    #[test]
+    fn parse_round_trips_structure(tree in tree()) {
+        let fs = parse(&transcript(&tree));
+        prop_assert_eq!(&from_fs(&fs, fs.root()), &tree);
+    }
+
+    #[test]
+    fn calc_size_max_matches_brute_force(tree in tree(), max in 0usize..2_000_000) {
+        let fs = parse(&transcript(&tree));
+        prop_assert_eq!(fs.calc_size_max(max), brute_force_size_max(&tree, max));
+    }
+}
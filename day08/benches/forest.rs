@@ -0,0 +1,71 @@
+use std::time::{Duration, Instant};
+
+use day08::{calculate_scenic_score, calculate_sightlines, count_visible, Height, Heights};
+
+// Small linear congruential generator so the forests are reproducible without extra crates.
+fn generate(size: usize, seed: u64) -> Heights {
+    let mut state = seed;
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| {
+                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    ((state >> 33) % 10) as Height
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Heights rising along every row and column: each tree looks back over up to
+// 127 shorter ones, the bad case for walking rays tree by tree.
+fn staircase(size: usize) -> Heights {
+    (0..size)
+        .map(|row| (0..size).map(|col| ((row + col) % 128) as Height).collect())
+        .collect()
+}
+
+fn per_tree_highest_score(heights: &Heights) -> u64 {
+    let mut score = 0;
+    for row in 0..heights.len() {
+        for col in 0..heights[row].len() {
+            score = score.max(calculate_scenic_score(heights, row, col));
+        }
+    }
+    score
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>6} {:>14} {:>14} {:>10} {:>16}",
+        "forest", "size", "sightlines", "per tree", "visible", "best score"
+    );
+    for (name, size) in [("random", 1_000), ("random", 5_000), ("staircase", 1_000), ("staircase", 5_000)] {
+        let heights = if name == "random" { generate(size, size as u64) } else { staircase(size) };
+        let (sightlines, sweep_time) = time(|| calculate_sightlines(&heights));
+        let best = sightlines.highest_scenic_score();
+        // The per-tree scan is only run where it finishes in reasonable time.
+        let per_tree_time = if size <= 1_000 {
+            let (reference, elapsed) = time(|| per_tree_highest_score(&heights));
+            assert_eq!(best, reference);
+            format!("{:?}", elapsed)
+        } else {
+            "-".to_string()
+        };
+        println!(
+            "{:>10} {:>6} {:>14?} {:>14} {:>10} {:>16}",
+            name,
+            size,
+            sweep_time,
+            per_tree_time,
+            count_visible(&sightlines.visible),
+            best
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+pub type Matrix<T> = Vec<Vec<T>>;
+pub type Height = i32;
+pub type Heights = Matrix<Height>;
+pub type Visible = Matrix<bool>;
+
+/// How tree heights are written in the input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Alphabet {
+    /// One decimal digit per tree, as in the puzzle.
+    Digits,
+    /// One hexadecimal digit per tree.
+    Hex,
+    /// One letter per tree, `a` lowest to `z` highest; `S` and `E` count as `a` and `z`
+    /// like in elevation maps.
+    Letters,
+    /// Whitespace-separated integers, possibly negative.
+    Integers,
+}
+
+impl FromStr for Alphabet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "digits" => Ok(Alphabet::Digits),
+            "hex" => Ok(Alphabet::Hex),
+            "letters" => Ok(Alphabet::Letters),
+            "ints" | "integers" => Ok(Alphabet::Integers),
+            _ => Err(format!("Unknown alphabet {}, expected digits, hex, letters or ints", s)),
+        }
+    }
+}
+
+impl Alphabet {
+    fn height(self, tree: char) -> Option<Height> {
+        match (self, tree) {
+            (Alphabet::Digits, _) => tree.to_digit(10).map(|height| height as Height),
+            (Alphabet::Hex, _) => tree.to_digit(16).map(|height| height as Height),
+            (Alphabet::Letters, 'S') => Some(0),
+            (Alphabet::Letters, 'E') => Some(25),
+            (Alphabet::Letters, 'a'..='z') => Some(tree as Height - 'a' as Height),
+            (Alphabet::Letters, _) | (Alphabet::Integers, _) => None,
+        }
+    }
+
+    /// The character a height is written as, if it has one in this alphabet.
+    pub fn symbol(self, height: Height) -> Option<char> {
+        let height = u32::try_from(height).ok()?;
+        match self {
+            Alphabet::Digits => char::from_digit(height, 10),
+            Alphabet::Hex => char::from_digit(height, 16),
+            Alphabet::Letters => (height < 26).then(|| char::from(b'a' + height as u8)),
+            Alphabet::Integers => None,
+        }
+    }
+
+    fn parse_row(self, line: &str) -> Result<Vec<Height>, String> {
+        if self == Alphabet::Integers {
+            return line
+                .split_whitespace()
+                .map(|tree| Height::from_str(tree).map_err(|_| format!("invalid height {:?}", tree)))
+                .collect();
+        }
+        line.chars()
+            .map(|tree| self.height(tree).ok_or_else(|| format!("invalid height {:?}", tree)))
+            .collect()
+    }
+}
+
+/// Parses a forest of decimal digits, panicking on malformed input.
+pub fn parse(content: &str) -> Heights {
+    parse_with(content, Alphabet::Digits).unwrap_or_else(|error| panic!("Error parsing forest: {}", error))
+}
+
+/// Parses a forest whose heights are written in `alphabet`. Every row must have
+/// the same number of trees; blank lines are only allowed at the end.
+pub fn parse_with(content: &str, alphabet: Alphabet) -> Result<Heights, String> {
+    let lines: Vec<&str> = content.lines().map(str::trim_end).collect();
+    let used = lines.iter().rposition(|line| !line.is_empty()).map_or(0, |last| last + 1);
+    parse_layer(&lines[..used], 1, alphabet)
+}
+
+// Parses consecutive rows, the first of them on line `first_line` of the input.
+pub(crate) fn parse_layer(lines: &[&str], first_line: usize, alphabet: Alphabet) -> Result<Heights, String> {
+    let mut heights: Heights = Vec::with_capacity(lines.len());
+    for (index, line) in lines.iter().enumerate() {
+        let line_number = first_line + index;
+        let row = alphabet.parse_row(line).map_err(|error| format!("Line {}: {}", line_number, error))?;
+        if let Some(first) = heights.first() {
+            if row.len() != first.len() {
+                return Err(format!(
+                    "Line {}: row has {} trees, expected {} like the first row",
+                    line_number,
+                    row.len(),
+                    first.len()
+                ));
+            }
+        } else if row.is_empty() {
+            return Err(format!("Line {}: empty row", line_number));
+        }
+        heights.push(row);
+    }
+
+    Ok(heights)
+}
+
+pub fn count_visible(visible: &Visible) -> usize {
+    let mut amount = 0;
+    for row in visible {
+        for tree in row {
+            if *tree {
+                amount += 1;
+            }
+        }
+    }
+    amount
+}
+
+/// Scenic score of a single tree by walking its four rays; O(n) per tree.
+pub fn calculate_scenic_score(heights: &Heights, row: usize, col: usize) -> u64 {
+    let tree = heights[row][col];
+    let line = &heights[row];
+
+    let mut score_left = 0;
+    for checked_tree in line[..col].iter().rev() {
+        score_left += 1;
+        if *checked_tree >= tree {
+            break;
+        }
+    }
+
+    let mut score_right = 0;
+    for checked_tree in &line[col + 1..] {
+        score_right += 1;
+        if *checked_tree >= tree {
+            break;
+        }
+    }
+
+    let mut score_top = 0;
+    for checked_row in heights[..row].iter().rev() {
+        score_top += 1;
+        if checked_row[col] >= tree {
+            break;
+        }
+    }
+
+    let mut score_bottom = 0;
+    for checked_row in &heights[row + 1..] {
+        score_bottom += 1;
+        if checked_row[col] >= tree {
+            break;
+        }
+    }
+
+    // println!("{} {} {} {} {} {}", row, col, score_left, score_top, score_right, score_bottom);
+
+    score_left * score_right * score_top * score_bottom
+}
@@ -0,0 +1,106 @@
+use crate::forest::{Height, Heights};
+
+/// A box of trees in `N` dimensions, addressed by one coordinate per axis.
+pub trait Grid<const N: usize> {
+    /// Extent along every axis.
+    fn shape(&self) -> [usize; N];
+    fn height(&self, cell: [usize; N]) -> Height;
+}
+
+impl Grid<2> for Heights {
+    fn shape(&self) -> [usize; 2] {
+        [self.len(), self.first().map_or(0, Vec::len)]
+    }
+
+    fn height(&self, [row, col]: [usize; 2]) -> Height {
+        self[row][col]
+    }
+}
+
+/// The cell one `step` away from `cell`, if it is still inside `shape`.
+pub fn step<const N: usize>(cell: [usize; N], step: [isize; N], shape: [usize; N]) -> Option<[usize; N]> {
+    let mut next = cell;
+    for axis in 0..N {
+        next[axis] = cell[axis].checked_add_signed(step[axis]).filter(|coordinate| *coordinate < shape[axis])?;
+    }
+    Some(next)
+}
+
+/// Every cell of `shape`, the last axis changing fastest.
+pub fn cells<const N: usize>(shape: [usize; N]) -> impl Iterator<Item = [usize; N]> {
+    let first = (!shape.contains(&0)).then_some([0; N]);
+    std::iter::successors(first, move |cell| {
+        let mut next = *cell;
+        for axis in (0..N).rev() {
+            next[axis] += 1;
+            if next[axis] < shape[axis] {
+                return Some(next);
+            }
+            next[axis] = 0;
+        }
+        None
+    })
+}
+
+/// The cells with nothing `direction` in front of them: those within `direction`
+/// of a face it leaves the grid through. Each cell is listed once.
+pub fn line_starts<const N: usize>(shape: [usize; N], direction: [isize; N]) -> impl Iterator<Item = [usize; N]> {
+    (0..N).filter(move |axis| direction[*axis] != 0).flat_map(move |axis| {
+        // The slab along `axis`, leaving out the cells already in the slabs of earlier axes.
+        let mut ranges = shape.map(|extent| 0..extent);
+        for (other, range) in ranges.iter_mut().enumerate().take(axis + 1) {
+            let delta = direction[other];
+            let depth = delta.unsigned_abs().min(shape[other]);
+            let slab = if delta > 0 { shape[other] - depth..shape[other] } else { 0..depth };
+            *range = match (other == axis, delta) {
+                (_, 0) => continue,
+                (true, _) => slab,
+                (false, delta) if delta > 0 => 0..slab.start,
+                (false, _) => slab.end..shape[other],
+            };
+        }
+        let offset = ranges.clone().map(|range| range.start);
+        cells(ranges.map(|range| range.len())).map(move |cell| {
+            let mut start = cell;
+            for axis in 0..N {
+                start[axis] += offset[axis];
+            }
+            start
+        })
+    })
+}
+
+/// One monotonic-stack pass over every line of `grid` along `direction`,
+/// O(cells) overall. Calls `record(cell, distance, sees_edge)` once per tree.
+///
+/// Each line is walked from the edge the trees look towards, keeping a stack
+/// of the trees not yet hidden behind a taller or equal one, tallest first.
+/// Popping everything smaller than the current tree leaves its blocker on
+/// top; an empty stack means it sees to the edge and is visible from there.
+pub fn sweep<const N: usize>(
+    grid: &impl Grid<N>,
+    direction: [isize; N],
+    mut record: impl FnMut([usize; N], u32, bool),
+) {
+    let shape = grid.shape();
+    let backwards = direction.map(|delta| -delta);
+    let mut stack: Vec<(Height, u32)> = Vec::new();
+    for start in line_starts(shape, direction) {
+        stack.clear();
+        let mut cell = Some(start);
+        let mut position = 0;
+        while let Some(current) = cell {
+            let tree = grid.height(current);
+            while stack.last().is_some_and(|(height, _)| *height < tree) {
+                stack.pop();
+            }
+            match stack.last() {
+                Some((_, blocker)) => record(current, position - blocker, false),
+                None => record(current, position, true),
+            }
+            stack.push((tree, position));
+            cell = step(current, backwards, shape);
+            position += 1;
+        }
+    }
+}
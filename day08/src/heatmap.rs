@@ -0,0 +1,75 @@
+use std::fmt::Write;
+
+use crate::forest::{Alphabet, Height, Heights, Matrix, Visible};
+
+const RESET: &str = "\x1b[0m";
+// Background for the best scenic spot. Every cell starts with `0;` so no
+// attribute of the previous cell carries over.
+const BEST: &str = "\x1b[0;1;97;41m";
+// The 24 grays of the 256-color palette, from dark to light.
+const GRAY_FIRST: u8 = 232;
+const GRAY_LAST: u8 = 255;
+
+// Palette index shading `height` between the lowest and highest tree.
+fn shade(height: Height, lowest: Height, highest: Height) -> u8 {
+    if highest == lowest {
+        return GRAY_LAST;
+    }
+    let span = (GRAY_LAST - GRAY_FIRST) as i64;
+    let step = (height as i64 - lowest as i64) * span / (highest as i64 - lowest as i64);
+    GRAY_FIRST + step as u8
+}
+
+/// ANSI heatmap of the forest: the background gets lighter with height,
+/// visible trees are printed in bold green, and `best` is marked in red.
+/// Trees are drawn with their symbol in `alphabet`, or `#` if they have none.
+pub fn render_heatmap(
+    heights: &Heights,
+    alphabet: Alphabet,
+    visible: &Visible,
+    best: Option<(usize, usize)>,
+) -> String {
+    let lowest = heights.iter().flatten().copied().min().unwrap_or(0);
+    let highest = heights.iter().flatten().copied().max().unwrap_or(0);
+
+    let mut out = String::new();
+    for (row, line) in heights.iter().enumerate() {
+        for (col, height) in line.iter().enumerate() {
+            let tree = alphabet.symbol(*height).unwrap_or('#');
+            if best == Some((row, col)) {
+                write!(out, "{}{}", BEST, tree).unwrap();
+                continue;
+            }
+            let foreground = if visible[row][col] { "0;1;32" } else { "0;2;30" };
+            write!(out, "\x1b[{};48;5;{}m{}", foreground, shade(*height, lowest, highest), tree).unwrap();
+        }
+        writeln!(out, "{}", RESET).unwrap();
+    }
+    out
+}
+
+/// Scenic scores as comma-separated rows.
+pub fn render_csv(scores: &Matrix<u64>) -> String {
+    let mut out = String::new();
+    for row in scores {
+        let cells: Vec<String> = row.iter().map(u64::to_string).collect();
+        writeln!(out, "{}", cells.join(",")).unwrap();
+    }
+    out
+}
+
+/// Binary 16-bit PGM (P5) of the scenic scores, scaled so the best spot is white.
+pub fn render_pgm(scores: &Matrix<u64>) -> Vec<u8> {
+    const MAX_VALUE: u64 = u16::MAX as u64;
+    let rows = scores.len();
+    let cols = scores.first().map_or(0, Vec::len);
+    let highest = scores.iter().flatten().copied().max().unwrap_or(0).max(1);
+
+    let mut out = format!("P5\n{} {}\n{}\n", cols, rows, MAX_VALUE).into_bytes();
+    out.reserve(rows * cols * 2);
+    for score in scores.iter().flatten() {
+        let value = (*score as u128 * MAX_VALUE as u128 / highest as u128) as u16;
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+    out
+}
@@ -0,0 +1,18 @@
+mod forest;
+mod grid;
+mod heatmap;
+mod sightlines;
+mod solution;
+mod volume;
+
+pub use forest::{
+    calculate_scenic_score, count_visible, parse, parse_with, Alphabet, Height, Heights, Matrix, Visible,
+};
+pub use grid::{sweep, Grid};
+pub use heatmap::{render_csv, render_heatmap, render_pgm};
+pub use sightlines::{
+    calculate_highest_scenic_score, calculate_sightlines, calculate_visibility, Combine, Direction, LineOfSight,
+    Sightlines, View,
+};
+pub use solution::{solve_part1, solve_part2};
+pub use volume::{calculate_volume_sightlines, parse_volume, Volume, VolumeSightlines, AXES};
@@ -1,160 +1,209 @@
+use std::env;
 use std::fs;
+use std::io::{self, Write};
+use std::str::FromStr;
 
-type Matrix<T> = Vec<Vec<T>>;
-type Heights = Matrix<i8>;
-type Visible = Matrix<bool>;
+use day08::{
+    calculate_highest_scenic_score, calculate_scenic_score, calculate_visibility, calculate_volume_sightlines, count_visible, parse,
+    parse_volume, parse_with, render_csv, render_heatmap, render_pgm, Alphabet, Combine, Heights, Sightlines, View,
+};
 
-fn parse(content: &String) -> Heights {
-    let mut heights: Heights = Vec::new();
+const FOREST_OPTIONS: &str =
+    "[--alphabet digits|hex|letters|ints] [--directions 4|8|knight] [--combine product|sum|min|max]";
 
-    for line in content.split("\n") {
-        let mut row: Vec<i8> = Vec::with_capacity(line.len());
-        for tree in line.chars() {
-            row.push(i8::from_str_radix(&tree.to_string(), 10).expect("Error parsing tree"));
-        }
-        heights.push(row);
-    }
-
-    return heights;
+fn part1(heights: &Heights) {
+    //println!("{:?}", heights);
+    //println!("{:?}", calculate_visibility(heights));
+    let visible = calculate_visibility(heights);
+    println!("Part 1: {}", count_visible(&visible));
 }
 
-fn calculate_visibility(heights: &Heights) -> Visible {
-    let mut visible: Visible = Vec::with_capacity(heights.len());
-    let row_size = heights[0].len();
-    for _ in 0..heights.len() {
-        visible.push(vec![false; row_size]);
-    }
+fn part2(heights: &Heights) {
+    println!("Part 2: {}", calculate_highest_scenic_score(heights));
+}
 
-    for row in 0..heights.len() {
-        let mut highest_tree = -1;
-        for col in 0..row_size {
-            let tree = heights[row][col];
-            if tree > highest_tree {
-                visible[row][col] = true;
-                highest_tree = tree;
-            }
-        }
-    }
-    for row in 0..heights.len() {
-        let mut highest_tree = -1;
-        for col in (0..row_size).rev() {
-            let tree = heights[row][col];
-            if tree > highest_tree {
-                visible[row][col] = true;
-                highest_tree = tree;
-            }
+// Splits `--alphabet`, `--directions` and `--combine` off the arguments,
+// returning them and the remaining arguments.
+fn forest_options(args: &[String], usage: &str) -> (Alphabet, View, Vec<String>) {
+    let fail = |message: String| -> ! {
+        eprintln!("{}", message);
+        eprintln!("Usage: {} {}", usage, FOREST_OPTIONS);
+        std::process::exit(2);
+    };
+    let mut alphabet = Alphabet::Digits;
+    let mut view = View::default();
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().unwrap_or_else(|| fail(format!("Missing value for {}", arg)));
+        match arg.as_str() {
+            "--alphabet" => alphabet = Alphabet::from_str(&value()).unwrap_or_else(|e| fail(e)),
+            "--directions" => view.directions = View::directions_from_str(&value()).unwrap_or_else(|e| fail(e)),
+            "--combine" => view.combine = Combine::from_str(&value()).unwrap_or_else(|e| fail(e)),
+            _ => rest.push(arg.clone()),
         }
     }
+    (alphabet, view, rest)
+}
 
-    for col in 0..row_size {
-        let mut highest_tree = -1;
-        for row in 0..heights.len() {
-            let tree = heights[row][col];
-            if tree > highest_tree {
-                visible[row][col] = true;
-                highest_tree = tree;
-            }
-        }
-    }
+fn read_forest(path: &str, alphabet: Alphabet) -> Heights {
+    let content = fs::read_to_string(path).expect("Cannot read file");
+    parse_with(&content, alphabet).unwrap_or_else(|error| {
+        eprintln!("{}: {}", path, error);
+        std::process::exit(1);
+    })
+}
 
-    for col in 0..row_size {
-        let mut highest_tree = -1;
-        for row in (0..heights.len()).rev() {
-            let tree = heights[row][col];
-            if tree > highest_tree {
-                visible[row][col] = true;
-                highest_tree = tree;
-            }
-        }
+fn print_summary(sightlines: &Sightlines) {
+    println!("Visible: {}", count_visible(&sightlines.visible));
+    if let Some((row, col, score)) = sightlines.best_spot() {
+        println!("Best scenic spot: row {}, col {} (score {})", row, col, score);
     }
-
-    return visible;
 }
 
-fn count_visible(visible: &Visible) -> usize {
-    let mut amount = 0;
-    for row in visible {
-        for tree in row {
-            if *tree {
-                amount += 1;
-            }
-        }
+fn score(args: &[String]) {
+    let usage = "day08 score <forest>";
+    let (alphabet, view, args) = forest_options(args, usage);
+    if args.len() != 1 {
+        eprintln!("Usage: {} {}", usage, FOREST_OPTIONS);
+        std::process::exit(2);
     }
-    return amount;
+    print_summary(&view.sightlines(&read_forest(&args[0], alphabet)));
 }
 
-fn calculate_scenic_score(heights: &Heights, row: usize, col: usize) -> u32 {
-    let tree = heights[row][col];
-    let row_width = heights[0].len();
-
-    let mut score_left = 0;
-    for icol in (0..col).rev() {
-        let checked_tree = heights[row][icol];
-        score_left += 1;
-        if checked_tree >= tree {
-            break;
-        }
+fn heatmap(args: &[String]) {
+    let usage = "day08 heatmap <forest>";
+    let (alphabet, view, args) = forest_options(args, usage);
+    if args.len() != 1 {
+        eprintln!("Usage: {} {}", usage, FOREST_OPTIONS);
+        std::process::exit(2);
     }
+    let heights = read_forest(&args[0], alphabet);
+    let sightlines = view.sightlines(&heights);
+    let best = sightlines.best_spot().map(|(row, col, _)| (row, col));
+    print!("{}", render_heatmap(&heights, alphabet, &sightlines.visible, best));
+    print_summary(&sightlines);
+}
 
-    let mut score_right = 0;
-    for icol in col+1..row_width {
-        let checked_tree = heights[row][icol];
-        score_right += 1;
-        if checked_tree >= tree {
-            break;
-        }
+fn export(args: &[String]) {
+    let usage = "day08 export <pgm|csv> <forest> [output]";
+    let (alphabet, view, args) = forest_options(args, usage);
+    if args.len() < 2 || args.len() > 3 {
+        eprintln!("Usage: {} {}", usage, FOREST_OPTIONS);
+        std::process::exit(2);
     }
-
-    let mut score_top = 0;
-    for irow in (0..row).rev() {
-        let checked_tree = heights[irow][col];
-        score_top += 1;
-        if checked_tree >= tree {
-            break;
+    let scores = view.sightlines(&read_forest(&args[1], alphabet)).scenic_scores();
+    let output = match args[0].as_str() {
+        "pgm" => render_pgm(&scores),
+        "csv" => render_csv(&scores).into_bytes(),
+        format => {
+            eprintln!("Unknown format {}", format);
+            std::process::exit(2);
         }
+    };
+    match args.get(2) {
+        Some(path) => fs::write(path, output).expect("Cannot write file"),
+        None => io::stdout().write_all(&output).expect("Cannot write output"),
     }
+}
 
-    let mut score_bottom = 0;
-    for irow in row+1..heights.len() {
-        let checked_tree = heights[irow][col];
-        score_bottom += 1;
-        if checked_tree >= tree {
-            break;
+fn inspect(args: &[String]) {
+    let usage = "day08 inspect <forest> --row R --col C";
+    let fail = || -> ! {
+        eprintln!("Usage: {} {}", usage, FOREST_OPTIONS);
+        std::process::exit(2);
+    };
+    let (alphabet, view, args) = forest_options(args, usage);
+    let (mut path, mut row, mut col) = (None, None, None);
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        let mut value = || rest.next().and_then(|value| usize::from_str(value).ok()).unwrap_or_else(|| fail());
+        match arg.as_str() {
+            "--row" => row = Some(value()),
+            "--col" => col = Some(value()),
+            _ if path.is_none() => path = Some(arg),
+            _ => fail(),
         }
     }
+    let (Some(path), Some(row), Some(col)) = (path, row, col) else { fail() };
+    let heights = read_forest(path, alphabet);
+    let lines = view.lines_of_sight(&heights, row, col).unwrap_or_else(|| {
+        eprintln!("Row {}, col {} is outside the forest", row, col);
+        std::process::exit(1);
+    });
+
+    println!("Tree at row {}, col {}: height {}", row, col, heights[row][col]);
+    for line in &lines {
+        let seen: Vec<String> = line
+            .seen
+            .iter()
+            .map(|(row, col)| format!("({},{})={}", row, col, heights[*row][*col]))
+            .collect();
+        let end = match line.blocker {
+            Some((row, col)) => format!("blocked by ({},{})", row, col),
+            None => "sees the edge".to_string(),
+        };
+        println!("  {:<15} {:>3} trees, {}: {}", line.direction.to_string(), line.distance(), end, seen.join(" "));
+    }
 
-    // println!("{} {} {} {} {} {}", row, col, score_left, score_top, score_right, score_bottom);
-
-    return score_left * score_right * score_top * score_bottom;
+    let score = view.score(&lines);
+    let swept = view.sightlines(&heights).scenic_score(row, col);
+    println!("Scenic score ({}): {}", view.combine, score);
+    println!("Stack sweep:  {}", swept);
+    let mut consistent = score == swept;
+    if view == View::default() {
+        let walked = calculate_scenic_score(&heights, row, col);
+        println!("calculate_scenic_score: {}", walked);
+        consistent &= score == walked;
+    }
+    if !consistent {
+        eprintln!("Scores disagree");
+        std::process::exit(1);
+    }
 }
 
-fn calculate_highest_scenic_score(heights: &Heights) -> u32 {
-    let row_size = heights[0].len();
-    let mut score = 0;
-    for row in 1..heights.len()-1 {
-        for col in 1..row_size-1 {
-            let tree_score = calculate_scenic_score(heights, row, col);
-            if tree_score > score {
-                score = tree_score;
+fn volume(args: &[String]) {
+    let usage = || -> ! {
+        eprintln!("Usage: day08 volume <forest> [--alphabet digits|hex|letters|ints]");
+        std::process::exit(2);
+    };
+    let mut alphabet = Alphabet::Digits;
+    let mut path = None;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--alphabet" => {
+                let value = rest.next().unwrap_or_else(|| usage());
+                alphabet = Alphabet::from_str(value).unwrap_or_else(|_| usage());
             }
+            _ if path.is_none() => path = Some(arg),
+            _ => usage(),
         }
     }
-
-    return score;
-}
-
-fn part1(heights: &Heights) {
-    //println!("{:?}", heights);
-    //println!("{:?}", calculate_visibility(heights));
-    let visible = calculate_visibility(heights);
-    println!("Part 1: {}", count_visible(&visible));
-}
-
-fn part2(heights: &Heights) {
-    println!("Part 2: {}", calculate_highest_scenic_score(heights));
+    let path = path.unwrap_or_else(|| usage());
+    let content = fs::read_to_string(path).expect("Cannot read file");
+    let volume = parse_volume(&content, alphabet).unwrap_or_else(|error| {
+        eprintln!("{}: {}", path, error);
+        std::process::exit(1);
+    });
+    let sightlines = calculate_volume_sightlines(&volume);
+    println!("Visible: {}", sightlines.count_visible());
+    if let Some(([layer, row, col], score)) = sightlines.best_spot() {
+        println!("Best scenic spot: layer {}, row {}, col {} (score {})", layer, row, col, score);
+    }
 }
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("score") => return score(&args[1..]),
+        Some("heatmap") => return heatmap(&args[1..]),
+        Some("export") => return export(&args[1..]),
+        Some("inspect") => return inspect(&args[1..]),
+        Some("volume") => return volume(&args[1..]),
+        _ => (),
+    }
+
     let files = vec!["sample.txt", "input.txt"];
     for file in files {
         println!("Reading {}", file);
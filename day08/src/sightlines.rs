@@ -0,0 +1,324 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::forest::{Height, Heights, Matrix, Visible};
+use crate::grid::{sweep, Grid};
+
+/// A viewing direction as the step from a tree to the next one it looks at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Direction {
+    pub d_row: isize,
+    pub d_col: isize,
+}
+
+impl Direction {
+    pub const LEFT: Direction = Direction::new(0, -1);
+    pub const RIGHT: Direction = Direction::new(0, 1);
+    pub const UP: Direction = Direction::new(-1, 0);
+    pub const DOWN: Direction = Direction::new(1, 0);
+
+    pub const CARDINAL: [Direction; 4] = [Direction::LEFT, Direction::RIGHT, Direction::UP, Direction::DOWN];
+    pub const EIGHT_WAY: [Direction; 8] = [
+        Direction::LEFT,
+        Direction::RIGHT,
+        Direction::UP,
+        Direction::DOWN,
+        Direction::new(-1, -1),
+        Direction::new(-1, 1),
+        Direction::new(1, -1),
+        Direction::new(1, 1),
+    ];
+    pub const KNIGHT: [Direction; 8] = [
+        Direction::new(-2, -1),
+        Direction::new(-2, 1),
+        Direction::new(-1, -2),
+        Direction::new(-1, 2),
+        Direction::new(1, -2),
+        Direction::new(1, 2),
+        Direction::new(2, -1),
+        Direction::new(2, 1),
+    ];
+
+    pub const fn new(d_row: isize, d_col: isize) -> Self {
+        Direction { d_row, d_col }
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction::new(-self.d_row, -self.d_col)
+    }
+
+    /// The cell one step away from `(row, col)`, if it is still inside a `rows` x `cols` grid.
+    pub fn step(self, row: usize, col: usize, rows: usize, cols: usize) -> Option<(usize, usize)> {
+        let row = row.checked_add_signed(self.d_row).filter(|row| *row < rows)?;
+        let col = col.checked_add_signed(self.d_col).filter(|col| *col < cols)?;
+        Some((row, col))
+    }
+}
+
+impl fmt::Display for Direction {
+    /// `up`, `down-left`, or `2 up, 1 left` for steps longer than one tree.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vertical = match self.d_row.signum() {
+            -1 => Some("up"),
+            1 => Some("down"),
+            _ => None,
+        };
+        let horizontal = match self.d_col.signum() {
+            -1 => Some("left"),
+            1 => Some("right"),
+            _ => None,
+        };
+        if self.d_row.abs() > 1 || self.d_col.abs() > 1 {
+            let parts: Vec<String> = [(self.d_row, vertical), (self.d_col, horizontal)]
+                .iter()
+                .filter_map(|(delta, name)| name.map(|name| format!("{} {}", delta.abs(), name)))
+                .collect();
+            return write!(f, "{}", parts.join(", "));
+        }
+        match (vertical, horizontal) {
+            (Some(vertical), Some(horizontal)) => write!(f, "{}-{}", vertical, horizontal),
+            (Some(name), None) | (None, Some(name)) => write!(f, "{}", name),
+            (None, None) => write!(f, "nowhere"),
+        }
+    }
+}
+
+/// What one tree sees looking in one direction.
+#[derive(Debug)]
+pub struct LineOfSight {
+    pub direction: Direction,
+    /// The trees it sees as `(row, col)`, nearest first, ending with the blocker if there is one.
+    pub seen: Vec<(usize, usize)>,
+    /// The first tree at least as tall, `None` if the view reaches the edge.
+    pub blocker: Option<(usize, usize)>,
+}
+
+impl LineOfSight {
+    pub fn distance(&self) -> u32 {
+        self.seen.len() as u32
+    }
+}
+
+/// How the viewing distances of a tree are combined into its scenic score.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Combine {
+    Product,
+    Sum,
+    Min,
+    Max,
+}
+
+impl Combine {
+    pub fn apply(self, distances: impl Iterator<Item = u64>) -> u64 {
+        match self {
+            Combine::Product => distances.product(),
+            Combine::Sum => distances.sum(),
+            Combine::Min => distances.min().unwrap_or(0),
+            Combine::Max => distances.max().unwrap_or(0),
+        }
+    }
+}
+
+impl fmt::Display for Combine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Combine::Product => write!(f, "product"),
+            Combine::Sum => write!(f, "sum"),
+            Combine::Min => write!(f, "min"),
+            Combine::Max => write!(f, "max"),
+        }
+    }
+}
+
+impl FromStr for Combine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(Combine::Product),
+            "sum" => Ok(Combine::Sum),
+            "min" => Ok(Combine::Min),
+            "max" => Ok(Combine::Max),
+            _ => Err(format!("Unknown combine function {}, expected product, sum, min or max", s)),
+        }
+    }
+}
+
+/// The directions trees look in and how their viewing distances make a scenic score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct View {
+    pub directions: Vec<Direction>,
+    pub combine: Combine,
+}
+
+impl Default for View {
+    /// The puzzle's view: the four cardinal directions, distances multiplied.
+    fn default() -> Self {
+        View::new(&Direction::CARDINAL, Combine::Product)
+    }
+}
+
+impl View {
+    pub fn new(directions: &[Direction], combine: Combine) -> Self {
+        View { directions: directions.to_vec(), combine }
+    }
+
+    /// Parses a set of directions: `4` (cardinal), `8` (with diagonals) or `knight`.
+    pub fn directions_from_str(s: &str) -> Result<Vec<Direction>, String> {
+        match s {
+            "4" | "cardinal" => Ok(Direction::CARDINAL.to_vec()),
+            "8" | "diagonal" => Ok(Direction::EIGHT_WAY.to_vec()),
+            "knight" => Ok(Direction::KNIGHT.to_vec()),
+            _ => Err(format!("Unknown directions {}, expected 4, 8 or knight", s)),
+        }
+    }
+
+    /// Walks every ray from `(row, col)`, listing the trees seen in each direction.
+    /// Returns `None` if the cell is outside the forest.
+    pub fn lines_of_sight(&self, heights: &Heights, row: usize, col: usize) -> Option<Vec<LineOfSight>> {
+        let [rows, cols] = heights.shape();
+        if row >= rows || col >= cols {
+            return None;
+        }
+        let tree = heights[row][col];
+        let lines = self
+            .directions
+            .iter()
+            .map(|direction| {
+                let mut line = LineOfSight { direction: *direction, seen: Vec::new(), blocker: None };
+                let mut cell = direction.step(row, col, rows, cols);
+                while let Some((next_row, next_col)) = cell {
+                    line.seen.push((next_row, next_col));
+                    if heights[next_row][next_col] >= tree {
+                        line.blocker = Some((next_row, next_col));
+                        break;
+                    }
+                    cell = direction.step(next_row, next_col, rows, cols);
+                }
+                line
+            })
+            .collect();
+        Some(lines)
+    }
+
+    /// The scenic score of the distances in `lines`.
+    pub fn score(&self, lines: &[LineOfSight]) -> u64 {
+        self.combine.apply(lines.iter().map(|line| u64::from(line.distance())))
+    }
+
+    /// Computes visibility and viewing distances with one stack sweep per direction,
+    /// O(rows * cols) per direction.
+    pub fn sightlines(&self, heights: &Heights) -> Sightlines {
+        let [rows, cols] = heights.shape();
+        let mut visible: Visible = vec![vec![false; cols]; rows];
+        let distances = self
+            .directions
+            .iter()
+            .map(|direction| sweep_direction(heights, *direction, &mut visible))
+            .collect();
+
+        Sightlines { visible, distances, view: self.clone() }
+    }
+}
+
+/// Visibility from outside and viewing distances of every tree, per direction.
+pub struct Sightlines {
+    pub visible: Visible,
+    /// `distances[d][row][col]` is how many trees `(row, col)` sees looking in `view.directions[d]`.
+    pub distances: Vec<Matrix<u32>>,
+    pub view: View,
+}
+
+impl Sightlines {
+    pub fn scenic_score(&self, row: usize, col: usize) -> u64 {
+        self.view
+            .combine
+            .apply(self.distances.iter().map(|distances| u64::from(distances[row][col])))
+    }
+
+    pub fn highest_scenic_score(&self) -> u64 {
+        self.best_spot().map_or(0, |(_, _, score)| score)
+    }
+
+    /// Row, column and score of the tree with the highest scenic score, the first one on ties.
+    pub fn best_spot(&self) -> Option<(usize, usize, u64)> {
+        let mut best: Option<(usize, usize, u64)> = None;
+        for row in 0..self.visible.len() {
+            for col in 0..self.visible[row].len() {
+                let score = self.scenic_score(row, col);
+                if best.is_none_or(|(_, _, highest)| score > highest) {
+                    best = Some((row, col, score));
+                }
+            }
+        }
+        best
+    }
+
+    pub fn scenic_scores(&self) -> Matrix<u64> {
+        (0..self.visible.len())
+            .map(|row| (0..self.visible[row].len()).map(|col| self.scenic_score(row, col)).collect())
+            .collect()
+    }
+}
+
+fn sweep_direction(heights: &Heights, direction: Direction, visible: &mut Visible) -> Matrix<u32> {
+    let [rows, cols] = heights.shape();
+    match direction {
+        Direction::LEFT => return sweep_rows(heights, rows, visible, |r, c| (r, c, r, c as u32)),
+        Direction::RIGHT => return sweep_rows(heights, rows, visible, |r, c| (r, cols - 1 - c, r, c as u32)),
+        Direction::UP => return sweep_rows(heights, cols, visible, |r, c| (r, c, c, r as u32)),
+        Direction::DOWN => return sweep_rows(heights, cols, visible, |r, c| (rows - 1 - r, c, c, r as u32)),
+        _ => {}
+    }
+    let mut distance = vec![vec![0u32; cols]; rows];
+    sweep(heights, [direction.d_row, direction.d_col], |[row, col], trees, sees_edge| {
+        distance[row][col] = trees;
+        visible[row][col] |= sees_edge;
+    });
+    distance
+}
+
+// The cardinal directions visit the trees row by row, which keeps memory access
+// sequential, instead of line by line like `sweep`. `locate` maps the visiting
+// order to the tree's cell, the line it looks along and its position on that line.
+fn sweep_rows(
+    heights: &Heights,
+    lines: usize,
+    visible: &mut Visible,
+    locate: impl Fn(usize, usize) -> (usize, usize, usize, u32),
+) -> Matrix<u32> {
+    let [rows, cols] = heights.shape();
+    let mut distance = vec![vec![0u32; cols]; rows];
+    let mut stacks: Vec<Vec<(Height, u32)>> = vec![Vec::new(); lines];
+    for row_index in 0..rows {
+        for col_index in 0..cols {
+            let (row, col, line, position) = locate(row_index, col_index);
+            let tree = heights[row][col];
+            let stack = &mut stacks[line];
+            while stack.last().is_some_and(|(height, _)| *height < tree) {
+                stack.pop();
+            }
+            match stack.last() {
+                Some((_, blocker)) => distance[row][col] = position - blocker,
+                None => {
+                    distance[row][col] = position;
+                    visible[row][col] = true;
+                }
+            }
+            stack.push((tree, position));
+        }
+    }
+    distance
+}
+
+pub fn calculate_sightlines(heights: &Heights) -> Sightlines {
+    View::default().sightlines(heights)
+}
+
+pub fn calculate_visibility(heights: &Heights) -> Visible {
+    calculate_sightlines(heights).visible
+}
+
+pub fn calculate_highest_scenic_score(heights: &Heights) -> u64 {
+    calculate_sightlines(heights).highest_scenic_score()
+}
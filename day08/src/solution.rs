@@ -0,0 +1,11 @@
+use crate::{calculate_highest_scenic_score, calculate_visibility, count_visible, parse};
+
+/// Number of trees visible from outside the grid.
+pub fn solve_part1(content: &str) -> String {
+    count_visible(&calculate_visibility(&parse(content))).to_string()
+}
+
+/// Highest scenic score of any tree.
+pub fn solve_part2(content: &str) -> String {
+    calculate_highest_scenic_score(&parse(content)).to_string()
+}
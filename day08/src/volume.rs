@@ -0,0 +1,107 @@
+use crate::forest::{parse_layer, Alphabet, Height, Heights, Matrix, Visible};
+use crate::grid::{cells, sweep, Grid};
+
+/// A 3D forest as a stack of equally sized layers, indexed `[layer][row][col]`.
+pub type Volume = Vec<Heights>;
+
+impl Grid<3> for Volume {
+    fn shape(&self) -> [usize; 3] {
+        let [rows, cols] = self.first().map_or([0, 0], Grid::shape);
+        [self.len(), rows, cols]
+    }
+
+    fn height(&self, [layer, row, col]: [usize; 3]) -> Height {
+        self[layer][row][col]
+    }
+}
+
+/// The six axis directions as `[layer, row, col]` steps.
+pub const AXES: [[isize; 3]; 6] = [[0, 0, -1], [0, 0, 1], [0, -1, 0], [0, 1, 0], [-1, 0, 0], [1, 0, 0]];
+
+/// Parses layers of rows separated by blank lines; all layers must have the same shape.
+pub fn parse_volume(content: &str, alphabet: Alphabet) -> Result<Volume, String> {
+    let lines: Vec<&str> = content.lines().map(str::trim_end).collect();
+    let mut volume: Volume = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        if lines[start].is_empty() {
+            start += 1;
+            continue;
+        }
+        let end = lines[start..].iter().position(|line| line.is_empty()).map_or(lines.len(), |len| start + len);
+        let layer = parse_layer(&lines[start..end], start + 1, alphabet)?;
+        if let Some(first) = volume.first() {
+            if first.shape() != layer.shape() {
+                let ([rows, cols], [first_rows, first_cols]) = (layer.shape(), first.shape());
+                return Err(format!(
+                    "Line {}: layer has {}x{} trees, expected {}x{} like the first layer",
+                    start + 1,
+                    rows,
+                    cols,
+                    first_rows,
+                    first_cols
+                ));
+            }
+        }
+        volume.push(layer);
+        start = end;
+    }
+    Ok(volume)
+}
+
+/// Visibility from outside and viewing distances of every tree in a volume, along the six axes.
+pub struct VolumeSightlines {
+    pub visible: Vec<Visible>,
+    /// `distances[d][layer][row][col]` is how many trees the tree sees looking along `AXES[d]`.
+    pub distances: Vec<Vec<Matrix<u32>>>,
+}
+
+impl VolumeSightlines {
+    pub fn shape(&self) -> [usize; 3] {
+        let rows = self.visible.first().map_or(0, Vec::len);
+        let cols = self.visible.first().and_then(|layer| layer.first()).map_or(0, Vec::len);
+        [self.visible.len(), rows, cols]
+    }
+
+    pub fn count_visible(&self) -> usize {
+        self.visible.iter().flatten().flatten().filter(|visible| **visible).count()
+    }
+
+    /// Product of the viewing distances along all six axes.
+    pub fn scenic_score(&self, [layer, row, col]: [usize; 3]) -> u64 {
+        self.distances
+            .iter()
+            .map(|distances| u64::from(distances[layer][row][col]))
+            .product()
+    }
+
+    /// Cell and score of the tree with the highest scenic score, the first one on ties.
+    pub fn best_spot(&self) -> Option<([usize; 3], u64)> {
+        let mut best: Option<([usize; 3], u64)> = None;
+        for cell in cells(self.shape()) {
+            let score = self.scenic_score(cell);
+            if best.is_none_or(|(_, highest)| score > highest) {
+                best = Some((cell, score));
+            }
+        }
+        best
+    }
+}
+
+pub fn calculate_volume_sightlines(volume: &Volume) -> VolumeSightlines {
+    let [layers, rows, cols] = volume.shape();
+    let mut visible: Vec<Visible> = vec![vec![vec![false; cols]; rows]; layers];
+    let distances = AXES
+        .iter()
+        .map(|direction| {
+            let mut distance = vec![vec![vec![0u32; cols]; rows]; layers];
+            sweep(volume, *direction, |[layer, row, col], trees, sees_edge| {
+                distance[layer][row][col] = trees;
+                visible[layer][row][col] |= sees_edge;
+            });
+            distance
+        })
+        .collect();
+
+    VolumeSightlines { visible, distances }
+}
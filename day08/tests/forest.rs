@@ -0,0 +1,65 @@
+use day08::{
+    calculate_highest_scenic_score, calculate_sightlines, calculate_visibility, count_visible, parse_with, Alphabet,
+};
+
+fn visible(content: &str, alphabet: Alphabet) -> usize {
+    count_visible(&calculate_visibility(&parse_with(content, alphabet).unwrap()))
+}
+
+fn highest(content: &str, alphabet: Alphabet) -> u64 {
+    calculate_highest_scenic_score(&parse_with(content, alphabet).unwrap())
+}
+
+#[test]
+fn empty_forest_has_nothing_to_see() {
+    for content in ["", "\n", "\n\n"] {
+        let heights = parse_with(content, Alphabet::Digits).unwrap();
+        assert!(heights.is_empty());
+        assert_eq!(visible(content, Alphabet::Digits), 0);
+        assert_eq!(highest(content, Alphabet::Digits), 0);
+        assert_eq!(calculate_sightlines(&heights).best_spot(), None);
+    }
+}
+
+#[test]
+fn single_row_or_column_is_all_edge() {
+    assert_eq!(visible("30373", Alphabet::Digits), 5);
+    assert_eq!(highest("30373", Alphabet::Digits), 0);
+    assert_eq!(visible("3\n0\n3\n7\n3\n", Alphabet::Digits), 5);
+    assert_eq!(highest("3\n0\n3\n7\n3\n", Alphabet::Digits), 0);
+    assert_eq!(visible("5", Alphabet::Digits), 1);
+}
+
+#[test]
+fn two_by_two_forest_is_all_edge() {
+    assert_eq!(visible("12\n34", Alphabet::Digits), 4);
+    assert_eq!(highest("12\n34", Alphabet::Digits), 0);
+}
+
+#[test]
+fn ragged_rows_are_reported_with_their_line() {
+    let error = parse_with("303\n25\n653\n", Alphabet::Digits).unwrap_err();
+    assert!(error.starts_with("Line 2: "), "{}", error);
+    let error = parse_with("30373\n25512\n6533\n", Alphabet::Digits).unwrap_err();
+    assert_eq!(error, "Line 3: row has 4 trees, expected 5 like the first row");
+    let error = parse_with("\n303\n", Alphabet::Digits).unwrap_err();
+    assert!(error.starts_with("Line 1: "), "{}", error);
+}
+
+#[test]
+fn hex_heights_go_up_to_f() {
+    // The 0 in the middle hides behind every neighbour and sees one tree each way.
+    let content = "1a2\nf0b\n3c4\n";
+    assert_eq!(visible(content, Alphabet::Hex), 8);
+    assert_eq!(highest(content, Alphabet::Hex), 1);
+    assert_eq!(parse_with(content, Alphabet::Hex).unwrap()[1], vec![15, 0, 11]);
+    assert!(parse_with(content, Alphabet::Digits).is_err());
+}
+
+#[test]
+fn letters_treat_start_and_end_as_a_and_z() {
+    let content = "Sbc\nzaE\nbcd\n";
+    assert_eq!(parse_with(content, Alphabet::Letters).unwrap()[1], vec![25, 0, 25]);
+    assert_eq!(visible(content, Alphabet::Letters), 8);
+    assert_eq!(highest(content, Alphabet::Letters), 1);
+}
@@ -0,0 +1,128 @@
+use day08::{calculate_volume_sightlines, sweep, Combine, Direction, Heights, View, Volume, AXES};
+
+// Small deterministic pseudo-random numbers, enough to vary shapes and heights.
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % bound as u64) as usize
+    }
+
+    // Few heights so that equal trees block each other often.
+    fn heights(&mut self, rows: usize, cols: usize) -> Heights {
+        (0..rows).map(|_| (0..cols).map(|_| self.below(5) as i32).collect()).collect()
+    }
+}
+
+// Walks from `cell` along `direction`: the trees seen and whether the edge was reached.
+fn ray<const N: usize>(
+    shape: [usize; N],
+    height: impl Fn([usize; N]) -> i32,
+    cell: [usize; N],
+    direction: [isize; N],
+) -> (u32, bool) {
+    let mut current = cell;
+    let mut trees = 0;
+    loop {
+        let mut next = current;
+        for axis in 0..N {
+            match current[axis].checked_add_signed(direction[axis]) {
+                Some(index) if index < shape[axis] => next[axis] = index,
+                _ => return (trees, true),
+            }
+        }
+        trees += 1;
+        if height(next) >= height(cell) {
+            return (trees, false);
+        }
+        current = next;
+    }
+}
+
+fn cells([layers, rows, cols]: [usize; 3]) -> impl Iterator<Item = [usize; 3]> {
+    let rows_of = move |layer| (0..rows).flat_map(move |row| (0..cols).map(move |col| [layer, row, col]));
+    (0..layers).flat_map(rows_of)
+}
+
+#[test]
+fn sightlines_match_lines_of_sight() {
+    let mut random = Lcg(7);
+    for _ in 0..200 {
+        let (rows, cols) = (1 + random.below(8), 1 + random.below(8));
+        let heights = random.heights(rows, cols);
+        for directions in [&Direction::CARDINAL[..], &Direction::EIGHT_WAY[..], &Direction::KNIGHT[..]] {
+            let view = View::new(directions, Combine::Product);
+            let sightlines = view.sightlines(&heights);
+            for row in 0..rows {
+                for col in 0..cols {
+                    let lines = view.lines_of_sight(&heights, row, col).unwrap();
+                    for (index, line) in lines.iter().enumerate() {
+                        assert_eq!(sightlines.distances[index][row][col], line.distance(), "{:?}", heights);
+                        let direction = [line.direction.d_row, line.direction.d_col];
+                        let expected = ray([rows, cols], |[row, col]| heights[row][col], [row, col], direction);
+                        assert_eq!((line.distance(), line.blocker.is_none()), expected, "{:?}", heights);
+                    }
+                    let visible = lines.iter().any(|line| line.blocker.is_none());
+                    assert_eq!(sightlines.visible[row][col], visible, "{:?}", heights);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn volume_sightlines_match_ray_walks() {
+    let mut random = Lcg(11);
+    for _ in 0..100 {
+        let (layers, rows, cols) = (1 + random.below(5), 1 + random.below(5), 1 + random.below(5));
+        let volume: Volume = (0..layers).map(|_| random.heights(rows, cols)).collect();
+        let shape = [layers, rows, cols];
+        let height = |[layer, row, col]: [usize; 3]| volume[layer][row][col];
+        let sightlines = calculate_volume_sightlines(&volume);
+
+        let mut best: Option<([usize; 3], u64)> = None;
+        let mut visible = 0;
+        for cell in cells(shape) {
+            let [layer, row, col] = cell;
+            let rays: Vec<(u32, bool)> = AXES.iter().map(|axis| ray(shape, height, cell, *axis)).collect();
+            for (index, (trees, _)) in rays.iter().enumerate() {
+                assert_eq!(sightlines.distances[index][layer][row][col], *trees, "{:?}", volume);
+            }
+            let sees_edge = rays.iter().any(|(_, edge)| *edge);
+            assert_eq!(sightlines.visible[layer][row][col], sees_edge, "{:?}", volume);
+            visible += usize::from(sees_edge);
+            let score = rays.iter().map(|(trees, _)| u64::from(*trees)).product();
+            assert_eq!(sightlines.scenic_score(cell), score);
+            if best.is_none_or(|(_, highest)| score > highest) {
+                best = Some((cell, score));
+            }
+        }
+        assert_eq!(sightlines.count_visible(), visible);
+        assert_eq!(sightlines.best_spot(), best);
+    }
+}
+
+#[test]
+fn sweep_follows_any_direction_in_three_dimensions() {
+    let mut random = Lcg(13);
+    let directions = [[1, 1, 1], [-1, 1, 0], [0, -2, 1], [1, 0, -3], [-2, -1, 1]];
+    for _ in 0..100 {
+        let (layers, rows, cols) = (1 + random.below(5), 1 + random.below(5), 1 + random.below(5));
+        let volume: Volume = (0..layers).map(|_| random.heights(rows, cols)).collect();
+        let shape = [layers, rows, cols];
+        let height = |[layer, row, col]: [usize; 3]| volume[layer][row][col];
+        for direction in directions {
+            let mut seen = vec![vec![vec![None; cols]; rows]; layers];
+            sweep(&volume, direction, |[layer, row, col], trees, sees_edge| {
+                assert!(seen[layer][row][col].is_none(), "{:?} recorded twice", [layer, row, col]);
+                seen[layer][row][col] = Some((trees, sees_edge));
+            });
+            for cell in cells(shape) {
+                let [layer, row, col] = cell;
+                let expected = ray(shape, height, cell, direction);
+                assert_eq!(seen[layer][row][col], Some(expected), "{:?} along {:?}", volume, direction);
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod rope;
+
+pub use rope::{
+    find_rope_positions, find_tail_positions, parse, solve_part1, solve_part2, Direction, Instruction,
+    InstructionParseError, ParseError, Position, TailMap,
+};
@@ -0,0 +1,178 @@
+use std::{collections::HashMap, num::ParseIntError, str::FromStr};
+
+pub type ParseError = String;
+pub type TailMap = HashMap<Position, bool>;
+
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct Position(pub i32, pub i32);
+
+#[derive(Debug)]
+pub enum Direction {
+    L,
+    U,
+    R,
+    D,
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "L" => Ok(Direction::L),
+            "U" => Ok(Direction::U),
+            "R" => Ok(Direction::R),
+            "D" => Ok(Direction::D),
+            _ => Err("Invalid Direction".to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InstructionParseError {
+    Dir(ParseError),
+    Amount(ParseIntError),
+}
+
+impl From<ParseError> for InstructionParseError {
+    fn from(value: ParseError) -> Self {
+        InstructionParseError::Dir(value)
+    }
+}
+
+impl From<ParseIntError> for InstructionParseError {
+    fn from(value: ParseIntError) -> Self {
+        InstructionParseError::Amount(value)
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub amount: i32,
+}
+
+impl FromStr for Instruction {
+    type Err = InstructionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split(' ').collect();
+        if parts.len() != 2 {
+            return Err(InstructionParseError::Dir(
+                "Invalid Instruction".to_string(),
+            ));
+        }
+
+        let direction = Direction::from_str(parts[0])?;
+        let amount = i32::from_str(parts[1])?;
+
+        Ok(Instruction { direction, amount })
+    }
+}
+
+pub fn parse(content: &str) -> Vec<Instruction> {
+    let mut result = Vec::new();
+
+    for line in content.lines().filter(|line| !line.is_empty()) {
+        result.push(Instruction::from_str(line).expect("Failed to parse line"))
+    }
+
+    result
+}
+
+fn move_head(head: &mut Position, direction: &Direction) {
+    match direction {
+        Direction::L => head.0 -= 1,
+        Direction::U => head.1 += 1,
+        Direction::R => head.0 += 1,
+        Direction::D => head.1 -= 1,
+    }
+}
+
+fn move_cord(visited: &mut TailMap, head: &Position, tail: &mut Position, track: bool) {
+    let mut changed = false;
+
+    if (head.0 - tail.0).abs() >= 2 {
+        if head.0 > tail.0 {
+            tail.0 += 1;
+        } else {
+            tail.0 -= 1;
+        }
+
+        if head.1 != tail.1 {
+            tail.1 = head.1
+        }
+
+        changed = true;
+    }
+
+    if (head.1 - tail.1).abs() >= 2 {
+        if head.1 > tail.1 {
+            tail.1 += 1;
+        } else {
+            tail.1 -= 1;
+        }
+
+        if head.0 != tail.0 {
+            tail.0 = head.0
+        }
+
+        changed = true;
+    }
+
+    if changed && track {
+        visited.insert(tail.clone(), true);
+    }
+}
+
+pub fn find_rope_positions(instructions: &[Instruction]) -> TailMap {
+    let mut rope = vec![Position(0, 0); 10];
+    // println!("{:?}", rope);
+    let mut tail_visited: TailMap = HashMap::new();
+    tail_visited.insert(Position(0, 0), true);
+
+    // let mut i = 0;
+    for instruction in instructions {
+        // println!("{:?}", instruction);
+        for _ in 0..instruction.amount {
+            move_head(&mut rope[0], &instruction.direction);
+            for pos in 1..rope.len() {
+                let prev_pos = rope[pos - 1].clone();
+                move_cord(&mut tail_visited, &prev_pos, &mut rope[pos], pos == 9);
+            }
+            // println!("{:?}", rope);
+        }
+        // if i > 2 {
+            // break;
+        // }
+        // i += 1;
+    }
+
+    tail_visited
+}
+
+pub fn find_tail_positions(instructions: &[Instruction]) -> TailMap {
+    let mut head = Position(0, 0);
+    let mut tail = Position(0, 0);
+    let mut tail_visited: TailMap = HashMap::new();
+    tail_visited.insert(Position(0, 0), true);
+
+    for instruction in instructions {
+        for _ in 0..instruction.amount {
+            move_head(&mut head, &instruction.direction);
+            move_cord(&mut tail_visited, &head, &mut tail, true);
+        }
+    }
+
+    tail_visited
+}
+
+/// Number of positions the tail of a two-knot rope visits.
+pub fn solve_part1(content: &str) -> String {
+    find_tail_positions(&parse(content)).len().to_string()
+}
+
+/// Number of positions the tail of a ten-knot rope visits.
+pub fn solve_part2(content: &str) -> String {
+    find_rope_positions(&parse(content)).len().to_string()
+}
@@ -0,0 +1,119 @@
+use std::{fmt, str::FromStr};
+
+/// Cycles whose signal strengths part 1 adds up.
+pub const SIGNAL_CYCLES: [i32; 6] = [20, 60, 100, 140, 180, 220];
+
+pub type Parsed = Vec<Instruction>;
+
+#[derive(Debug)]
+pub enum Instruction {
+    Noop,
+    AddX(i32),
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "noop" {
+            return Ok(Instruction::Noop);
+        }
+        let parts: Vec<_> = s.split(' ').collect();
+        if parts.len() != 2 {
+            return Err("Invalid instruction".to_string());
+        }
+        match (parts[0], parts[1]) {
+            ("addx", value) => Ok(Instruction::AddX(i32::from_str(value).map_err(|_| "Invlid param".to_string())?)),
+            _ => Err("Invalid instruction".to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct State {
+    pub x: i32,
+    pub cycle: i32,
+}
+
+pub type Sceen = Vec<Vec<char>>;
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Noop => write!(f, "noop"),
+            Instruction::AddX(value) => write!(f, "addx {}", value),
+        }
+    }
+}
+
+impl Instruction {
+    pub fn cycles(&self) -> i32 {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::AddX(_) => 2,
+        }
+    }
+
+    pub fn apply(&self, state: State) -> State {
+        let cycle = state.cycle + self.cycles();
+        match self {
+            Instruction::Noop => State{x: state.x, cycle},
+            Instruction::AddX(value) => State { x: state.x + value, cycle},
+        }
+    }
+}
+
+impl State {
+    pub fn strength(&self) -> i32 {
+        self.x * self.cycle
+    }
+}
+
+pub fn get_state_after_cycles(instructions: &Parsed, cycles: i32) -> State {
+    let mut state = State{x: 1, cycle: 1};
+
+    for instruction in instructions {
+        let i_cycles = instruction.cycles();
+        if state.cycle + i_cycles > cycles {
+            return State{x: state.x, cycle: cycles};
+        }
+        state = instruction.apply(state);
+    }
+
+    state
+}
+
+pub fn summarize_cycles(instructions: &Parsed, cycles: &[i32]) -> i32 {
+    let mut sum = 0;
+    for cycle in cycles {
+        sum += get_state_after_cycles(instructions, *cycle).strength();
+    }
+    sum
+}
+
+pub fn render(instructions: &Parsed) -> Sceen {
+    let mut screen = vec![vec!['.'; 40]; 6];
+
+    let mut cycle = 1;
+    for line in screen.iter_mut() {
+        for col in 0..40u16 {
+            let state = get_state_after_cycles(instructions, cycle);
+            let icol = i32::from(col);
+            if (state.x - 1..=state.x + 1).contains(&icol) {
+                line[usize::from(col)] = '#';
+            }
+
+            cycle += 1;
+        }
+    }
+
+    screen
+}
+
+pub fn parse(content: &str) -> Parsed {
+    let mut instructions: Parsed = Vec::new();
+    for line in content.lines().filter(|line| !line.is_empty()) {
+        instructions.push(Instruction::from_str(line).expect("Failed to parse line"))
+    }
+    instructions
+}
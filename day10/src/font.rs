@@ -0,0 +1,39 @@
+// The CRT screen, in pixels.
+pub const WIDTH: usize = 40;
+pub const HEIGHT: usize = 6;
+
+// The AoC CRT font, 4 columns per letter plus one column of spacing.
+pub const FONT: [(char, [&str; HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+    (' ', ["....", "....", "....", "....", "....", "...."]),
+];
+
+/// Reads the letters drawn on a screen in the CRT font, `None` if any glyph is unknown.
+pub fn read_screen(screen: &[Vec<char>]) -> Option<String> {
+    if screen.len() != HEIGHT || screen.iter().any(|line| line.len() != WIDTH) {
+        return None;
+    }
+    let text: String = (0..WIDTH / 5)
+        .map(|index| {
+            let glyph: Vec<String> = screen.iter().map(|line| line[index * 5..index * 5 + 4].iter().collect()).collect();
+            FONT.iter().find(|(_, font)| font.iter().eq(glyph.iter())).map(|(letter, _)| *letter)
+        })
+        .collect::<Option<String>>()?;
+    Some(text.trim_end().to_string())
+}
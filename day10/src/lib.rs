@@ -0,0 +1,11 @@
+mod cpu;
+mod font;
+mod reverse;
+mod solution;
+
+pub use cpu::{
+    get_state_after_cycles, parse, render, summarize_cycles, Instruction, Parsed, Sceen, State, SIGNAL_CYCLES,
+};
+pub use font::read_screen;
+pub use reverse::{mismatches, render_text, run as run_reverse, synthesize};
+pub use solution::{solve_part1, solve_part2};
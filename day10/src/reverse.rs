@@ -0,0 +1,184 @@
+use std::fs;
+
+use crate::cpu::{render, Instruction, Parsed, Sceen};
+use crate::font::{FONT, HEIGHT, WIDTH};
+
+const CYCLES: usize = WIDTH * HEIGHT;
+
+// Sprite positions worth considering: -2 and 41 light nothing on screen,
+// everything in between lights at least one column.
+const X_MIN: i32 = -2;
+const X_MAX: i32 = WIDTH as i32 + 1;
+const X_COUNT: usize = (X_MAX - X_MIN + 1) as usize;
+
+fn parse_image(content: &str) -> Result<Sceen, String> {
+    let image: Sceen = content
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().collect())
+        .collect();
+
+    if image.len() != HEIGHT {
+        return Err(format!("Expected {} rows, got {}", HEIGHT, image.len()));
+    }
+    for (row, line) in image.iter().enumerate() {
+        if line.len() != WIDTH {
+            return Err(format!("Row {} has {} columns, expected {}", row, line.len(), WIDTH));
+        }
+        if let Some(pixel) = line.iter().find(|pixel| **pixel != '#' && **pixel != '.') {
+            return Err(format!("Invalid pixel '{}' in row {}", pixel, row));
+        }
+    }
+
+    Ok(image)
+}
+
+/// Draws `text` in the CRT font, `Err` for letters without a glyph or text wider than the screen.
+pub fn render_text(text: &str) -> Result<Sceen, String> {
+    let mut image = vec![vec!['.'; WIDTH]; HEIGHT];
+
+    for (index, letter) in text.to_uppercase().chars().enumerate() {
+        let glyph = FONT
+            .iter()
+            .find(|(c, _)| *c == letter)
+            .map(|(_, glyph)| glyph)
+            .ok_or(format!("No glyph for '{}'", letter))?;
+        let offset = index * 5;
+        if offset + 4 > WIDTH {
+            return Err(format!("Text '{}' does not fit on the screen", text));
+        }
+        for (row, line) in glyph.iter().enumerate() {
+            for (col, pixel) in line.chars().enumerate() {
+                image[row][offset + col] = pixel;
+            }
+        }
+    }
+
+    Ok(image)
+}
+
+fn pixel_cost(image: &Sceen, cycle: usize, x: i32) -> u32 {
+    let col = (cycle % WIDTH) as i32;
+    let lit = (x - 1..=x + 1).contains(&col);
+    let wanted = image[cycle / WIDTH][cycle % WIDTH] == '#';
+    u32::from(lit != wanted)
+}
+
+#[derive(Clone, Copy)]
+enum Step {
+    End,
+    Noop,
+    AddX(i32),
+}
+
+/// Finds the program with the fewest wrong pixels (and, among those, the fewest
+/// instructions) by dynamic programming over (cycle, X) pairs.
+pub fn synthesize(image: &Sceen) -> Parsed {
+    // best[cycle][x] = (wrong pixels, instructions, step) for cycles cycle..CYCLES
+    // when X is x at the start of cycle.
+    let mut best = vec![[(0u32, 0u32, Step::End); X_COUNT]; CYCLES + 1];
+
+    for cycle in (0..CYCLES).rev() {
+        // Cheapest way to continue after an addx finishes, with its target X.
+        let after_addx = if cycle + 2 <= CYCLES {
+            (0..X_COUNT)
+                .map(|w| (best[cycle + 2][w].0, best[cycle + 2][w].1, w))
+                .min_by_key(|(cost, count, _)| (*cost, *count))
+        } else {
+            None
+        };
+
+        let (done, rest) = best.split_at_mut(cycle + 1);
+        for (v, slot) in done[cycle].iter_mut().enumerate() {
+            let x = X_MIN + v as i32;
+            let here = pixel_cost(image, cycle, x);
+            let next = rest[0][v];
+            let mut choice = (here + next.0, next.1 + 1, Step::Noop);
+
+            if let Some((cost, count, w)) = after_addx {
+                let cost = here + pixel_cost(image, cycle + 1, x) + cost;
+                if (cost, count + 1) < (choice.0, choice.1) {
+                    choice = (cost, count + 1, Step::AddX(w as i32 - v as i32));
+                }
+            }
+
+            *slot = choice;
+        }
+    }
+
+    let mut program: Parsed = Vec::new();
+    let mut cycle = 0;
+    let mut v = (1 - X_MIN) as usize;
+    loop {
+        match best[cycle][v].2 {
+            Step::End => break,
+            Step::Noop => {
+                program.push(Instruction::Noop);
+                cycle += 1;
+            }
+            Step::AddX(delta) => {
+                program.push(Instruction::AddX(delta));
+                v = (v as i32 + delta) as usize;
+                cycle += 2;
+            }
+        }
+    }
+
+    program
+}
+
+/// Pixels of `screen` that differ from `image`, as (row, col).
+pub fn mismatches(image: &Sceen, screen: &Sceen) -> Vec<(usize, usize)> {
+    let mut wrong = Vec::new();
+    for row in 0..HEIGHT {
+        for col in 0..WIDTH {
+            if image[row][col] != screen[row][col] {
+                wrong.push((row, col));
+            }
+        }
+    }
+    wrong
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: day10 reverse <image file>");
+    eprintln!("       day10 reverse --text <TEXT>");
+    std::process::exit(2);
+}
+
+pub fn run(args: &[String]) {
+    let image = match args {
+        [flag, text] if flag == "--text" => render_text(text),
+        [file] => {
+            let content = fs::read_to_string(file).expect("Cannot read file");
+            parse_image(&content)
+        }
+        _ => usage(),
+    };
+    let image = image.unwrap_or_else(|error| {
+        eprintln!("Invalid image: {}", error);
+        std::process::exit(1);
+    });
+
+    let program = synthesize(&image);
+    for instruction in &program {
+        println!("{}", instruction);
+    }
+
+    let screen = render(&program);
+    let wrong = mismatches(&image, &screen);
+    if wrong.is_empty() {
+        eprintln!("All pixels satisfied with {} instructions", program.len());
+        return;
+    }
+
+    eprintln!("{} pixels cannot be drawn with a 3-wide sprite:", wrong.len());
+    for (row, col) in wrong {
+        eprintln!("  row {}, col {}: wanted '{}'", row, col, image[row][col]);
+    }
+    eprintln!("Closest possible image:");
+    for line in &screen {
+        eprintln!("{}", line.iter().collect::<String>());
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{parse, read_screen, render, summarize_cycles, SIGNAL_CYCLES};
+
+/// Sum of the signal strengths during the `SIGNAL_CYCLES`.
+pub fn solve_part1(content: &str) -> String {
+    summarize_cycles(&parse(content), &SIGNAL_CYCLES).to_string()
+}
+
+/// The letters on the screen, or the screen itself if it does not show any.
+pub fn solve_part2(content: &str) -> String {
+    let screen = render(&parse(content));
+    read_screen(&screen).unwrap_or_else(|| {
+        let lines: Vec<String> = screen.iter().map(|line| line.iter().collect()).collect();
+        lines.join("\n")
+    })
+}
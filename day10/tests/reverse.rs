@@ -0,0 +1,26 @@
+use day10::{mismatches, read_screen, render, render_text, synthesize};
+
+#[test]
+fn synthesized_program_draws_the_text() {
+    let image = render_text("EHZRBFPA").unwrap();
+    let screen = render(&synthesize(&image));
+    assert_eq!(screen, image);
+    assert_eq!(read_screen(&screen).as_deref(), Some("EHZRBFPA"));
+    assert!(mismatches(&image, &screen).is_empty());
+}
+
+#[test]
+fn infeasible_pixels_are_reported() {
+    // X is 1 during the first two cycles whatever the program, lighting the first two pixels.
+    let image = vec![vec!['.'; 40]; 6];
+    let screen = render(&synthesize(&image));
+    assert_eq!(mismatches(&image, &screen), vec![(0, 0), (0, 1)]);
+}
+
+#[test]
+fn text_needs_glyphs_and_room() {
+    assert!(render_text("ABC").is_ok());
+    assert_eq!(render_text("HELLO WORLD"), Err("No glyph for 'W'".to_string()));
+    assert_eq!(render_text("ABCEFGHI").map(|image| image.len()), Ok(6));
+    assert_eq!(render_text("ABCEFGHIJ"), Err("Text 'ABCEFGHIJ' does not fit on the screen".to_string()));
+}
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+/// Computes the answer to one part from the content of an input file.
+pub type Solver = fn(&str) -> String;
+
+/// A day the runner knows how to solve. Days 1 to 5 are written in
+/// TypeScript and run with Deno, so they are not part of it.
+pub struct Day {
+    pub number: u32,
+    pub inputs: &'static [&'static str],
+    pub parts: [Solver; 2],
+}
+
+impl Day {
+    /// The directory the day lives in, next to the runner.
+    pub fn directory(&self) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join(format!("day{:02}", self.number))
+    }
+}
+
+pub const DAYS: [Day; 5] = [
+    Day {
+        number: 6,
+        inputs: &["sample.txt", "samples.txt", "input.txt"],
+        parts: [day06::solve_part1, day06::solve_part2],
+    },
+    Day { number: 7, inputs: &["sample.txt", "input.txt"], parts: [day07::solve_part1, day07::solve_part2] },
+    Day { number: 8, inputs: &["sample.txt", "input.txt"], parts: [day08::solve_part1, day08::solve_part2] },
+    Day {
+        number: 9,
+        inputs: &["sample.txt", "sample2.txt", "input.txt"],
+        parts: [day09::solve_part1, day09::solve_part2],
+    },
+    Day { number: 10, inputs: &["sample.txt", "input.txt"], parts: [day10::solve_part1, day10::solve_part2] },
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,34 @@
+use std::{collections::HashMap, str::FromStr};
+
+/// Known answers, one per line as `day part input answer`, blank lines and
+/// lines starting with `#` ignored.
+#[derive(Debug, Default)]
+pub struct Expected(HashMap<(u32, usize, String), String>);
+
+impl Expected {
+    pub fn get(&self, day: u32, part: usize, input: &str) -> Option<&str> {
+        self.0.get(&(day, part, input.to_string())).map(String::as_str)
+    }
+}
+
+impl FromStr for Expected {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = HashMap::new();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parts: Vec<&str> = line.splitn(4, ' ').collect();
+            let [day, part, input, answer] = parts[..] else {
+                return Err(format!("Line {}: expected `day part input answer`, got {:?}", index + 1, line));
+            };
+            let day = u32::from_str(day).map_err(|_| format!("Line {}: invalid day {:?}", index + 1, day))?;
+            let part = usize::from_str(part).map_err(|_| format!("Line {}: invalid part {:?}", index + 1, part))?;
+            answers.insert((day, part, input.to_string()), answer.to_string());
+        }
+        Ok(Expected(answers))
+    }
+}
@@ -0,0 +1,108 @@
+use std::{env, fs, io, panic, path::PathBuf, str::FromStr, thread, time::Instant};
+
+use days::{Day, DAYS};
+use expected::Expected;
+use task::Task;
+
+mod days;
+mod expected;
+mod pool;
+mod report;
+mod task;
+mod watch;
+
+fn usage() -> ! {
+    eprintln!("Usage: runner run (--all | <day>...) [--jobs N] [--format table|json|junit|tsv] [--expected FILE]");
+    eprintln!("  Runs both parts of the days on all their inputs in parallel and compares the");
+    eprintln!("  answers with FILE, runner/expected.txt by default");
+    eprintln!("Usage: runner watch <day>");
+    eprintln!("  Runs the day again whenever a file in its directory changes and shows what changed");
+    std::process::exit(2);
+}
+
+fn run(args: &[String]) {
+    let mut days: Vec<&'static Day> = Vec::new();
+    let mut all = false;
+    let mut jobs = thread::available_parallelism().map_or(1, usize::from);
+    let mut format = "table";
+    let mut expected_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("expected.txt");
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--jobs" => {
+                let count = args.next().and_then(|jobs| usize::from_str(jobs).ok());
+                jobs = count.filter(|jobs| *jobs > 0).unwrap_or_else(|| usage());
+            }
+            "--format" => match args.next().map(String::as_str) {
+                Some(name @ ("table" | "json" | "junit" | "tsv")) => format = name,
+                _ => usage(),
+            },
+            "--expected" => expected_path = args.next().map(PathBuf::from).unwrap_or_else(|| usage()),
+            day => match u32::from_str(day).ok().and_then(days::find) {
+                Some(day) => days.push(day),
+                None => {
+                    eprintln!("Unknown day {:?}, known days are {:?}", day, DAYS.map(|day| day.number));
+                    usage();
+                }
+            },
+        }
+    }
+    if all {
+        days = DAYS.iter().collect();
+    }
+    if days.is_empty() {
+        usage();
+    }
+
+    let expected = match fs::read_to_string(&expected_path) {
+        Ok(content) => Expected::from_str(&content).unwrap_or_else(|error| {
+            eprintln!("{}: {}", expected_path.display(), error);
+            std::process::exit(2);
+        }),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Expected::default(),
+        Err(error) => panic!("Cannot read {}: {}", expected_path.display(), error),
+    };
+
+    // Panics are reported with the results, not on stderr as they happen.
+    panic::set_hook(Box::new(|_| {}));
+    let tasks = Task::all(&days, &expected);
+    let start = Instant::now();
+    let outcomes = pool::run_all(&tasks, jobs, Task::execute);
+    let wall = start.elapsed();
+    let _ = panic::take_hook();
+
+    match format {
+        "json" => print!("{}", report::render_json(&tasks, &outcomes, wall)),
+        "junit" => print!("{}", report::render_junit(&tasks, &outcomes, wall)),
+        "tsv" => print!("{}", report::render_tsv(&tasks, &outcomes)),
+        _ => print!("{}", report::render_table(&tasks, &outcomes, wall)),
+    }
+    for (task, outcome) in tasks.iter().zip(&outcomes) {
+        if !outcome.status.is_success() {
+            let Task { day, part, input, .. } = task;
+            eprintln!("Day {} part {} on {}: {} ({})", day.number, part, input, outcome.answer, outcome.status);
+        }
+    }
+    if outcomes.iter().any(|outcome| !outcome.status.is_success()) {
+        std::process::exit(1);
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("watch") => {
+            let day = match args.get(1..) {
+                Some([day]) => u32::from_str(day).ok().and_then(days::find).unwrap_or_else(|| usage()),
+                _ => usage(),
+            };
+            if let Err(error) = watch::watch(day) {
+                eprintln!("Cannot watch day {}: {}", day.number, error);
+                std::process::exit(1);
+            }
+        }
+        _ => usage(),
+    }
+}
@@ -0,0 +1,32 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Runs `work` on every item with `workers` threads, returning the results
+/// in the order of `items` whatever order they finish in.
+pub fn run_all<T: Sync, R: Send>(items: &[T], workers: usize, work: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else { break };
+                sender.send((index, work(item))).expect("Result receiver is gone");
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    for (index, result) in receiver {
+        results[index] = Some(result);
+    }
+    results.into_iter().map(|result| result.expect("A task did not report back")).collect()
+}
@@ -0,0 +1,186 @@
+use std::{fmt::Write, time::Duration};
+
+use day07::json_string;
+
+use crate::task::{Outcome, Status, Task};
+
+/// Milliseconds with microsecond precision.
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+// Answers spanning several lines, like a rendered screen, do not fit in a cell.
+fn cell(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        lines => format!("({} lines)", lines),
+    }
+}
+
+fn count(outcomes: &[Outcome], status: Status) -> usize {
+    outcomes.iter().filter(|outcome| outcome.status == status).count()
+}
+
+/// Table with one row per task and a line of totals.
+pub fn render_table(tasks: &[Task], outcomes: &[Outcome], wall: Duration) -> String {
+    let header = ["Day", "Part", "Input", "Answer", "Expected", "Time", "Status"].map(String::from);
+    let mut rows = vec![header];
+    for (task, outcome) in tasks.iter().zip(outcomes) {
+        rows.push([
+            task.day.number.to_string(),
+            task.part.to_string(),
+            task.input.to_string(),
+            cell(&outcome.answer),
+            task.expected.as_deref().map_or_else(|| "-".to_string(), cell),
+            format_duration(outcome.duration),
+            outcome.status.to_string(),
+        ]);
+    }
+    let mut widths = [0; 7];
+    for row in &rows {
+        for (width, text) in widths.iter_mut().zip(row) {
+            *width = (*width).max(text.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in &rows {
+        let cells: Vec<String> = row.iter().zip(widths).map(|(text, width)| format!("{:<width$}", text)).collect();
+        writeln!(out, "{}", cells.join("  ").trim_end()).unwrap();
+    }
+    let busy: Duration = outcomes.iter().map(|outcome| outcome.duration).sum();
+    writeln!(
+        out,
+        "{} passed, {} failed, {} unchecked, {} errors; {} of work in {} wall time",
+        count(outcomes, Status::Passed),
+        count(outcomes, Status::Failed),
+        count(outcomes, Status::Unchecked),
+        count(outcomes, Status::Missing) + count(outcomes, Status::Panicked),
+        format_duration(busy),
+        format_duration(wall)
+    )
+    .unwrap();
+    out
+}
+
+/// JSON export: `{"wall_ms", "results"}` with one
+/// `{"day", "part", "input", "answer", "expected", "duration_ms", "status"}` per task.
+/// `answer` holds the error message for tasks that did not produce one.
+pub fn render_json(tasks: &[Task], outcomes: &[Outcome], wall: Duration) -> String {
+    let mut out = String::new();
+    writeln!(out, "{{\"wall_ms\": {:.3}, \"results\": [", wall.as_secs_f64() * 1000.0).unwrap();
+    for (index, (task, outcome)) in tasks.iter().zip(outcomes).enumerate() {
+        write!(
+            out,
+            concat!(
+                "  {{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \"expected\": {}, ",
+                "\"duration_ms\": {:.3}, \"status\": \"{}\"}}"
+            ),
+            task.day.number,
+            task.part,
+            json_string(task.input),
+            json_string(&outcome.answer),
+            task.expected.as_deref().map_or_else(|| "null".to_string(), json_string),
+            outcome.duration.as_secs_f64() * 1000.0,
+            outcome.status
+        )
+        .unwrap();
+        out.push_str(if index + 1 == tasks.len() { "\n" } else { ",\n" });
+    }
+    out.push_str("]}\n");
+    out
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\n' => escaped.push_str("&#10;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// JUnit XML report with a test suite per day and a test case per part and input.
+/// Wrong answers are failures, unreadable inputs and panics are errors.
+pub fn render_junit(tasks: &[Task], outcomes: &[Outcome], wall: Duration) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let failures = count(outcomes, Status::Failed);
+    let errors = count(outcomes, Status::Missing) + count(outcomes, Status::Panicked);
+    writeln!(
+        out,
+        "<testsuites name=\"aoc2022\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.6}\">",
+        tasks.len(),
+        failures,
+        errors,
+        wall.as_secs_f64()
+    )
+    .unwrap();
+
+    let results: Vec<(&Task, &Outcome)> = tasks.iter().zip(outcomes).collect();
+    for suite in results.chunk_by(|(a, _), (b, _)| a.day.number == b.day.number) {
+        let name = format!("day{:02}", suite[0].0.day.number);
+        let outcomes: Vec<&Outcome> = suite.iter().map(|(_, outcome)| *outcome).collect();
+        let status_count = |status| outcomes.iter().filter(|outcome| outcome.status == status).count();
+        let time: Duration = outcomes.iter().map(|outcome| outcome.duration).sum();
+        writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.6}\">",
+            name,
+            suite.len(),
+            status_count(Status::Failed),
+            status_count(Status::Missing) + status_count(Status::Panicked),
+            time.as_secs_f64()
+        )
+        .unwrap();
+        for (task, outcome) in suite {
+            write!(
+                out,
+                "    <testcase classname=\"{}\" name=\"part {} on {}\" time=\"{:.6}\">",
+                name,
+                task.part,
+                xml_escape(task.input),
+                outcome.duration.as_secs_f64()
+            )
+            .unwrap();
+            match outcome.status {
+                Status::Passed | Status::Unchecked => {}
+                Status::Failed => write!(
+                    out,
+                    "<failure message=\"expected {}, got {}\"/>",
+                    xml_escape(task.expected.as_deref().unwrap_or_default()),
+                    xml_escape(&outcome.answer)
+                )
+                .unwrap(),
+                Status::Missing | Status::Panicked => write!(
+                    out,
+                    "<error type=\"{}\" message=\"{}\"/>",
+                    outcome.status,
+                    xml_escape(&outcome.answer)
+                )
+                .unwrap(),
+            }
+            writeln!(out, "<system-out>{}</system-out></testcase>", xml_escape(&outcome.answer)).unwrap();
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// One `day part input status answer` line per task, tab separated, with
+/// backslashes, tabs and newlines in the answer escaped. Read back by `watch`.
+pub fn render_tsv(tasks: &[Task], outcomes: &[Outcome]) -> String {
+    let mut out = String::new();
+    for (task, outcome) in tasks.iter().zip(outcomes) {
+        let answer = outcome.answer.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n");
+        let Task { day, part, input, .. } = task;
+        writeln!(out, "{}\t{}\t{}\t{}\t{}", day.number, part, input, outcome.status, answer).unwrap();
+    }
+    out
+}
@@ -0,0 +1,105 @@
+use std::{
+    any::Any,
+    fmt, fs,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use crate::{days::Day, expected::Expected};
+
+/// One part of one day run on one input file.
+pub struct Task {
+    pub day: &'static Day,
+    pub part: usize,
+    pub input: &'static str,
+    pub expected: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    /// The answer is the expected one.
+    Passed,
+    /// The answer is not the expected one.
+    Failed,
+    /// There is no expected answer to compare with.
+    Unchecked,
+    /// The input file could not be read.
+    Missing,
+    Panicked,
+}
+
+impl Status {
+    /// Whether the task ran without a wrong answer or an error.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Status::Passed | Status::Unchecked)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Status::Passed => "passed",
+            Status::Failed => "failed",
+            Status::Unchecked => "unchecked",
+            Status::Missing => "missing",
+            Status::Panicked => "panicked",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+pub struct Outcome {
+    pub status: Status,
+    /// The answer, or what went wrong.
+    pub answer: String,
+    pub duration: Duration,
+}
+
+// The message a panic was started with, if it had one.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "panicked without a message".to_string(),
+        },
+    }
+}
+
+impl Task {
+    /// Every part of `days` on every one of their inputs, day by day.
+    pub fn all(days: &[&'static Day], expected: &Expected) -> Vec<Task> {
+        let mut tasks = Vec::new();
+        for day in days {
+            for input in day.inputs {
+                for part in 1..=day.parts.len() {
+                    let expected = expected.get(day.number, part, input).map(String::from);
+                    tasks.push(Task { day, part, input, expected });
+                }
+            }
+        }
+        tasks
+    }
+
+    /// Solves the task, turning a panic into a `Panicked` outcome. The time
+    /// includes reading the input.
+    pub fn execute(&self) -> Outcome {
+        let start = Instant::now();
+        let content = match fs::read_to_string(self.day.directory().join(self.input)) {
+            Ok(content) => content,
+            Err(error) => {
+                return Outcome { status: Status::Missing, answer: error.to_string(), duration: start.elapsed() };
+            }
+        };
+        let solver = self.day.parts[self.part - 1];
+        let (status, answer) = match panic::catch_unwind(AssertUnwindSafe(|| solver(&content))) {
+            Ok(answer) => match &self.expected {
+                Some(expected) if *expected == answer => (Status::Passed, answer),
+                Some(_) => (Status::Failed, answer),
+                None => (Status::Unchecked, answer),
+            },
+            Err(payload) => (Status::Panicked, panic_message(payload)),
+        };
+        Outcome { status, answer, duration: start.elapsed() }
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use inotify::{Inotify, WatchMask};
+
+use crate::days::Day;
+
+// Editors write a file in several steps; changes this close together are one edit.
+const SETTLE: Duration = Duration::from_millis(200);
+
+// What one task of a run reported.
+#[derive(PartialEq)]
+struct Answer {
+    part: usize,
+    input: String,
+    status: String,
+    answer: String,
+}
+
+// Whether a change to `name` can change the answers, skipping editor swap and backup files.
+fn is_relevant(name: &Path) -> bool {
+    matches!(name.extension().and_then(|extension| extension.to_str()), Some("rs" | "txt" | "toml"))
+}
+
+fn unescape(answer: &str) -> String {
+    let mut out = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+// Runs the day in a fresh runner, so edits to its sources are compiled in.
+// `None` if it could not be built; cargo has explained why on stderr by then.
+fn run_day(day: &Day) -> Option<Vec<Answer>> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let output = Command::new(env!("CARGO"))
+        .args(["run", "-q", "--manifest-path"])
+        .arg(manifest_dir.join("Cargo.toml"))
+        .args(["--", "run", &day.number.to_string(), "--format", "tsv"])
+        // A separate target directory, so the build does not replace the running watcher.
+        .env("CARGO_TARGET_DIR", manifest_dir.join("target").join("watch"))
+        .stderr(Stdio::inherit())
+        .output()
+        .expect("Cannot start cargo");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.is_empty() {
+        return None;
+    }
+
+    let mut answers = Vec::new();
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.splitn(5, '\t').collect();
+        let [_, part, input, status, answer] = fields[..] else {
+            panic!("Unexpected runner output {:?}", line);
+        };
+        let part = part.parse().expect("Invalid part in runner output");
+        let (input, status, answer) = (input.to_string(), status.to_string(), unescape(answer));
+        answers.push(Answer { part, input, status, answer });
+    }
+    Some(answers)
+}
+
+fn show(answer: &str) -> String {
+    answer.replace('\n', "\n    ")
+}
+
+fn find<'a>(answers: &'a [Answer], part: usize, input: &str) -> Option<&'a Answer> {
+    answers.iter().find(|answer| answer.part == part && answer.input == input)
+}
+
+// Prints how the answers changed since `previous`, or all of them on the first run.
+fn print_diff(previous: Option<&[Answer]>, current: &[Answer]) {
+    for now in current {
+        let label = format!("Part {} on {}", now.part, now.input);
+        match previous.and_then(|previous| find(previous, now.part, &now.input)) {
+            None => println!("  {}: {} ({})", label, show(&now.answer), now.status),
+            Some(before) if before == now => println!("  {}: unchanged ({})", label, now.status),
+            Some(before) => println!(
+                "* {}: {} ({}) -> {} ({})",
+                label,
+                show(&before.answer),
+                before.status,
+                show(&now.answer),
+                now.status
+            ),
+        }
+    }
+    for before in previous.unwrap_or_default() {
+        if find(current, before.part, &before.input).is_none() {
+            println!("- Part {} on {}: no longer run", before.part, before.input);
+        }
+    }
+}
+
+/// Runs `day` whenever a source, input or manifest file in its directory changes,
+/// printing how the answers differ from the previous run. Only returns on an
+/// inotify error.
+pub fn watch(day: &Day) -> io::Result<()> {
+    let directory = day.directory().canonicalize()?;
+    let mut inotify = Inotify::init()?;
+    let mask = WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE | WatchMask::DELETE;
+    for watched in [directory.clone(), directory.join("src")] {
+        inotify.watches().add(&watched, mask)?;
+    }
+
+    println!("Watching {}", directory.display());
+    let mut previous = run_day(day);
+    if let Some(answers) = &previous {
+        print_diff(None, answers);
+    }
+    let mut buffer = [0; 4096];
+    loop {
+        let mut changed: Vec<PathBuf> = Vec::new();
+        let events = inotify.read_events_blocking(&mut buffer)?;
+        changed.extend(events.filter_map(|event| event.name.map(PathBuf::from)));
+        // Collect whatever else belongs to the same edit.
+        thread::sleep(SETTLE);
+        match inotify.read_events(&mut buffer) {
+            Ok(events) => changed.extend(events.filter_map(|event| event.name.map(PathBuf::from))),
+            Err(error) if error.kind() == ErrorKind::WouldBlock => {}
+            Err(error) => return Err(error),
+        }
+        changed.retain(|name| is_relevant(name));
+        changed.sort();
+        changed.dedup();
+        if changed.is_empty() {
+            continue;
+        }
+
+        let names: Vec<String> = changed.iter().map(|name| name.display().to_string()).collect();
+        println!("Changed: {}", names.join(", "));
+        match run_day(day) {
+            Some(answers) => {
+                print_diff(previous.as_deref(), &answers);
+                previous = Some(answers);
+            }
+            None => println!("  Build failed, keeping the previous answers"),
+        }
+    }
+}